@@ -63,6 +63,7 @@ fn bench_cell_to_boundary(c: &mut Criterion) {
             let opts = a5::core::cell::CellToBoundaryOptions {
                 closed_ring: true,
                 segments: Some(10),
+                ..Default::default()
             };
             black_box(a5::cell_to_boundary(black_box(cell), Some(opts)).unwrap())
         })
@@ -14,6 +14,7 @@ use a5::core::hex::u64_to_hex;
 use a5::core::serialization::{cell_to_children, WORLD_CELL};
 use serde_json::json;
 use std::env;
+use std::error::Error;
 use std::fs;
 use std::process;
 
@@ -51,7 +52,7 @@ fn main() {
     }
 }
 
-fn generate_wireframe(resolution: i32, output_file: &str) -> Result<usize, String> {
+fn generate_wireframe(resolution: i32, output_file: &str) -> Result<usize, Box<dyn Error>> {
     // Calculate total number of cells at this resolution
     let cell_ids = cell_to_children(WORLD_CELL, Some(resolution))?;
 
@@ -62,6 +63,7 @@ fn generate_wireframe(resolution: i32, output_file: &str) -> Result<usize, Strin
         let boundary_options = CellToBoundaryOptions {
             closed_ring: true,
             segments: Some(1),
+            ..Default::default()
         };
 
         let boundary = cell_to_boundary(*cell_id, Some(boundary_options))?;
@@ -12,6 +12,8 @@
 /// This type provides safe handling of degree values with appropriate
 /// normalization for longitude and latitude coordinates.
 #[derive(Debug, PartialEq, Copy, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
 pub struct Degrees(pub f64);
 
 impl Degrees {
@@ -52,6 +54,8 @@ impl From<Degrees> for f64 {
 /// This type provides safe handling of radian values commonly used
 /// in mathematical calculations and coordinate transformations.
 #[derive(Debug, PartialEq, Copy, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
 pub struct Radians(pub f64);
 
 impl Radians {
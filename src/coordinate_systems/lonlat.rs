@@ -12,6 +12,7 @@ use super::base::Degrees;
 /// antimeridian-spanning coordinates. Latitude values are clamped
 /// to [-90, 90] range.
 #[derive(Clone, Copy, Default, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LonLat {
     /// Longitude, in degrees.
     pub longitude: Degrees,
@@ -5,8 +5,9 @@
 use crate::coordinate_systems::{Cartesian, Face, LonLat, Spherical};
 use crate::core::constants::PI_OVER_5;
 use crate::core::coordinate_transforms::{
-    face_to_ij, from_lon_lat, normalize_longitudes, to_lon_lat, to_polar,
+    face_to_ij, from_lon_lat, normalize_longitudes, to_cartesian, to_lon_lat, to_polar,
 };
+use crate::core::error::A5Error;
 use crate::core::origin::{
     find_nearest_origin, find_nearest_origin_cartesian, quintant_to_segment, segment_to_quintant,
 };
@@ -16,10 +17,12 @@ use crate::core::tiling::{
     get_quintant_vertices,
 };
 use crate::core::utils::{A5Cell, Origin, OriginId};
+use crate::geometry::antimeridian::split_antimeridian;
 use crate::geometry::pentagon::PentagonShape;
 use crate::lattice::{ij_to_s, s_to_cell};
 use crate::projections::dodecahedron::DodecahedronProjection;
 use crate::traversal::global_neighbors::get_global_cell_neighbors;
+use crate::utils::great_circle::{cross_track_distance, short_arc_distance};
 use crate::utils::spiral::{Spiral, SPIRAL_SAMPLE_COUNT};
 use std::cell::RefCell;
 use std::collections::HashSet;
@@ -41,7 +44,7 @@ thread_local! {
 }
 
 /// Update the single-entry cache with a successful (cell, cell_id) pair.
-fn cache_result(cell: &A5Cell, cell_id: u64, resolution: i32) -> Result<u64, String> {
+fn cache_result(cell: &A5Cell, cell_id: u64, resolution: i32) -> Result<u64, A5Error> {
     let pentagon = get_pentagon(cell)?;
     let origin_id = cell.origin_id;
     LAST_RESULT.with(|c| {
@@ -56,7 +59,7 @@ fn cache_result(cell: &A5Cell, cell_id: u64, resolution: i32) -> Result<u64, Str
 }
 
 /// Convert lon/lat coordinates to A5 cell ID
-pub fn lonlat_to_cell(lonlat: LonLat, resolution: i32) -> Result<u64, String> {
+pub fn lonlat_to_cell(lonlat: LonLat, resolution: i32) -> Result<u64, A5Error> {
     spherical_to_cell(from_lon_lat(lonlat), resolution)
 }
 
@@ -65,7 +68,7 @@ pub fn lonlat_to_cell(lonlat: LonLat, resolution: i32) -> Result<u64, String> {
 /// `from_lon_lat` or `to_spherical(authalic_cartesian)`). Skips the redundant
 /// authalic inverse/forward round-trip in dense-sample loops where the input
 /// already comes from authalic Cartesian space (e.g. polygon-fill boundary slerp).
-pub fn spherical_to_cell(spherical: Spherical, resolution: i32) -> Result<u64, String> {
+pub fn spherical_to_cell(spherical: Spherical, resolution: i32) -> Result<u64, A5Error> {
     // Resolution -1 represents WORLD_CELL, which covers the entire world
     if resolution == -1 {
         return Ok(WORLD_CELL);
@@ -79,7 +82,7 @@ pub fn spherical_to_cell(spherical: Spherical, resolution: i32) -> Result<u64, S
 
     // Try the cached pentagon first — skips the full estimate pipeline when
     // consecutive calls land in the same cell (common in dense-sample loops).
-    let cached_hit = LAST_RESULT.with(|c| -> Result<Option<u64>, String> {
+    let cached_hit = LAST_RESULT.with(|c| -> Result<Option<u64>, A5Error> {
         let last_ref = c.borrow();
         let last = match last_ref.as_ref() {
             Some(l) if l.resolution == resolution => l,
@@ -167,14 +170,14 @@ const SPIRAL_SCALE_RAD: f64 = 70.0 * std::f64::consts::PI / 180.0;
 /// The ij_to_s function uses the triangular lattice which only approximates the pentagon lattice
 /// Thus these functions only return a cell nearby, and we need to search the neighbourhood to find the correct cell
 /// TODO: Implement a more accurate function
-fn spherical_to_estimate(spherical: Spherical, resolution: i32) -> Result<A5Cell, String> {
+fn spherical_to_estimate(spherical: Spherical, resolution: i32) -> Result<A5Cell, A5Error> {
     let origin = find_nearest_origin(spherical);
     let dodecahedron = DodecahedronProjection::get_thread_local();
     let dodec_point = dodecahedron.forward(spherical, origin.id)?;
     face_to_estimate(dodec_point, origin, resolution)
 }
 
-fn cartesian_to_estimate(cartesian: Cartesian, resolution: i32) -> Result<A5Cell, String> {
+fn cartesian_to_estimate(cartesian: Cartesian, resolution: i32) -> Result<A5Cell, A5Error> {
     let origin = find_nearest_origin_cartesian(cartesian);
     let dodecahedron = DodecahedronProjection::get_thread_local();
     let dodec_point = dodecahedron.forward_cartesian(cartesian, origin.id)?;
@@ -185,7 +188,7 @@ fn face_to_estimate(
     mut dodec_point: Face,
     origin: &Origin,
     resolution: i32,
-) -> Result<A5Cell, String> {
+) -> Result<A5Cell, A5Error> {
     let polar = to_polar(dodec_point);
     let quintant = get_quintant_polar(polar);
     let (segment, orientation) = quintant_to_segment(quintant, origin);
@@ -229,7 +232,7 @@ fn face_to_estimate(
 }
 
 /// Get the pentagon shape for a given A5 cell
-pub fn get_pentagon(cell: &A5Cell) -> Result<PentagonShape, String> {
+pub fn get_pentagon(cell: &A5Cell) -> Result<PentagonShape, A5Error> {
     let (quintant, orientation) = segment_to_quintant(cell.segment, cell.origin());
 
     if cell.resolution == FIRST_HILBERT_RESOLUTION - 1 {
@@ -252,7 +255,7 @@ pub fn get_pentagon(cell: &A5Cell) -> Result<PentagonShape, String> {
 }
 
 /// Convert A5 cell ID to spherical coordinates of cell center
-pub fn cell_to_spherical(cell: u64) -> Result<crate::coordinate_systems::Spherical, String> {
+pub fn cell_to_spherical(cell: u64) -> Result<crate::coordinate_systems::Spherical, A5Error> {
     let cell_data = deserialize(cell)?;
     let dodecahedron = DodecahedronProjection::get_thread_local();
     if cell_data.resolution >= FIRST_HILBERT_RESOLUTION {
@@ -274,7 +277,7 @@ pub fn cell_to_spherical(cell: u64) -> Result<crate::coordinate_systems::Spheric
 }
 
 /// Convert A5 cell ID to lon/lat coordinates of cell center
-pub fn cell_to_lonlat(cell: u64) -> Result<LonLat, String> {
+pub fn cell_to_lonlat(cell: u64) -> Result<LonLat, A5Error> {
     // WORLD_CELL represents the entire world, return (0, 0) as a reasonable default
     if cell == WORLD_CELL {
         return Ok(LonLat::new(0.0, 0.0));
@@ -283,12 +286,75 @@ pub fn cell_to_lonlat(cell: u64) -> Result<LonLat, String> {
     Ok(to_lon_lat(cell_to_spherical(cell)?))
 }
 
-/// Options for cell boundary generation
+/// Winding order of a cell boundary, as seen on a map with north up
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Winding {
+    /// Counter-clockwise, as required for RFC 7946 exterior rings
+    #[default]
+    CounterClockwise,
+    /// Clockwise, the order of the cell's pentagon vertices
+    Clockwise,
+}
+
+impl Winding {
+    /// Order of `PentagonShape` vertices, and so of the corners and edges of
+    /// every cell: clockwise, leaving the cell to the right of each edge
+    pub const PENTAGON: Winding = Winding::Clockwise;
+}
+
+/// Which corner a cell boundary starts from, or for an open ring ends at
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BoundaryStart {
+    /// Vertex 0 of the cell, where edge 0 starts (see `cell_to_edges`)
+    #[default]
+    FirstVertex,
+    /// The corner with the lexicographically smallest (longitude, latitude),
+    /// as in GEOS `normalize`, which depends only on the cell's geometry
+    SmallestCoordinate,
+}
+
+/// Options for cell boundary generation.
+///
+/// Fill in the fields that matter and take the rest from `Default`, either
+/// with a struct literal ending in `..Default::default()` or with the
+/// `with_*` methods:
+///
+/// ```
+/// use a5::{CellToBoundaryOptions, Winding};
+///
+/// let options = CellToBoundaryOptions {
+///     closed_ring: false,
+///     ..Default::default()
+/// };
+/// assert_eq!(
+///     options.with_winding(Winding::Clockwise),
+///     CellToBoundaryOptions::default()
+///         .with_closed_ring(false)
+///         .with_winding(Winding::Clockwise)
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct CellToBoundaryOptions {
-    /// Pass true to close the ring with the first point (default: true)
+    /// Pass true to close the ring with the first point. An open ring is the
+    /// closed ring without its first point, so it ends at the start corner
+    /// (default: true)
     pub closed_ring: bool,
     /// Number of segments to use for each edge. Pass None to use the resolution of the cell (default: None)
     pub segments: Option<i32>,
+    /// Winding order of the ring (default: `Winding::CounterClockwise`)
+    pub winding: Winding,
+    /// Corner the ring starts from (default: `BoundaryStart::FirstVertex`)
+    pub start: BoundaryStart,
+    /// Split edges until no segment is longer than this many meters along
+    /// the great circle between its ends. Overrides `segments` (default: None)
+    pub max_segment_length_m: Option<f64>,
+    /// Split edges until no segment strays further than this many meters from
+    /// the true, projected cell edge. Overrides `segments` (default: None)
+    pub max_deviation_m: Option<f64>,
 }
 
 impl Default for CellToBoundaryOptions {
@@ -296,15 +362,116 @@ impl Default for CellToBoundaryOptions {
         Self {
             closed_ring: true,
             segments: None,
+            winding: Winding::default(),
+            start: BoundaryStart::default(),
+            max_segment_length_m: None,
+            max_deviation_m: None,
+        }
+    }
+}
+
+impl CellToBoundaryOptions {
+    /// Set `closed_ring`
+    pub fn with_closed_ring(mut self, closed_ring: bool) -> Self {
+        self.closed_ring = closed_ring;
+        self
+    }
+
+    /// Set `segments`
+    pub fn with_segments(mut self, segments: i32) -> Self {
+        self.segments = Some(segments);
+        self
+    }
+
+    /// Set `winding`
+    pub fn with_winding(mut self, winding: Winding) -> Self {
+        self.winding = winding;
+        self
+    }
+
+    /// Set `start`
+    pub fn with_start(mut self, start: BoundaryStart) -> Self {
+        self.start = start;
+        self
+    }
+
+    /// Set `max_segment_length_m`
+    pub fn with_max_segment_length_m(mut self, max_segment_length_m: f64) -> Self {
+        self.max_segment_length_m = Some(max_segment_length_m);
+        self
+    }
+
+    /// Set `max_deviation_m`
+    pub fn with_max_deviation_m(mut self, max_deviation_m: f64) -> Self {
+        self.max_deviation_m = Some(max_deviation_m);
+        self
+    }
+}
+
+/// Most segments per edge chosen for a tolerance; tolerances needing more,
+/// e.g. below the floating point precision of the projection, are met as
+/// closely as this allows
+pub const MAX_BOUNDARY_SEGMENTS: usize = 1 << 16;
+
+fn check_tolerance(tolerance: Option<f64>) -> Result<f64, A5Error> {
+    match tolerance {
+        None => Ok(f64::INFINITY),
+        Some(t) if t > 0.0 && t.is_finite() => Ok(t),
+        Some(t) => Err(A5Error::InvalidTolerance(t)),
+    }
+}
+
+/// Fewest segments per edge, the same for every edge of the cell, so that
+/// no segment exceeds `max_length` meters and none deviates from the true
+/// edge by more than `max_deviation` meters.
+///
+/// Each round splits the edges in two as many segments as tried, so that the
+/// odd samples are the midpoints of the tried segments, where the projected
+/// edge is furthest from the great circle between their ends. Lengths fall
+/// with the number of segments and deviations with its square, which gives
+/// the next count to try.
+fn tolerance_segments(
+    pentagon: &PentagonShape,
+    origin_id: OriginId,
+    max_length: f64,
+    max_deviation: f64,
+) -> Result<usize, A5Error> {
+    let dodecahedron = DodecahedronProjection::get_thread_local();
+    let mut segments = 1;
+    loop {
+        let samples = pentagon
+            .split_edges(2 * segments)
+            .get_vertices_vec()
+            .iter()
+            .map(|&vertex| Ok(to_cartesian(dodecahedron.inverse(vertex, origin_id)?)))
+            .collect::<Result<Vec<Cartesian>, A5Error>>()?;
+
+        let n = samples.len();
+        let mut length_ratio: f64 = 0.0;
+        let mut deviation_ratio: f64 = 0.0;
+        for i in (0..n).step_by(2) {
+            let (a, midpoint, b) = (samples[i], samples[i + 1], samples[(i + 2) % n]);
+            length_ratio = length_ratio.max(short_arc_distance(a, b) / max_length);
+            deviation_ratio =
+                deviation_ratio.max(cross_track_distance(midpoint, a, b) / max_deviation);
+        }
+        if (length_ratio <= 1.0 && deviation_ratio <= 1.0) || segments >= MAX_BOUNDARY_SEGMENTS {
+            return Ok(segments);
         }
+
+        let needed = (segments as f64 * length_ratio.max(deviation_ratio.sqrt())).ceil() as usize;
+        segments = needed.clamp(segments + 1, MAX_BOUNDARY_SEGMENTS);
     }
 }
 
-/// Convert A5 cell ID to boundary coordinates
+/// Convert A5 cell ID to boundary coordinates.
+///
+/// The output is a pure function of the cell and the options, so boundaries
+/// are bit-identical across calls and can be hashed or compared directly.
 pub fn cell_to_boundary(
     cell_id: u64,
     options: Option<CellToBoundaryOptions>,
-) -> Result<Vec<LonLat>, String> {
+) -> Result<Vec<LonLat>, A5Error> {
     // WORLD_CELL represents the entire world and is unbounded
     if cell_id == WORLD_CELL {
         return Ok(Vec::new());
@@ -313,15 +480,26 @@ pub fn cell_to_boundary(
     let opts = options.unwrap_or_default();
     let cell_data = deserialize(cell_id)?;
 
-    let segments = opts
-        .segments
-        .unwrap_or_else(|| std::cmp::max(1, 2_i32.pow((6 - cell_data.resolution).max(0) as u32)));
-
     let pentagon = get_pentagon(&cell_data)?;
 
+    let segments = if opts.max_segment_length_m.is_some() || opts.max_deviation_m.is_some() {
+        tolerance_segments(
+            &pentagon,
+            cell_data.origin_id,
+            check_tolerance(opts.max_segment_length_m)?,
+            check_tolerance(opts.max_deviation_m)?,
+        )?
+    } else {
+        opts.segments
+            .unwrap_or_else(|| {
+                std::cmp::max(1, 2_i32.pow((6 - cell_data.resolution).max(0) as u32))
+            })
+            .max(1) as usize
+    };
+
     // Split each edge into segments before projection
     // Important to do before projection to obtain equal area cells
-    let split_pentagon = pentagon.split_edges(segments as usize);
+    let split_pentagon = pentagon.split_edges(segments);
     let vertices = split_pentagon.get_vertices_vec();
 
     // Unproject to obtain lon/lat coordinates
@@ -340,19 +518,78 @@ pub fn cell_to_boundary(
     // Normalize longitudes to handle antimeridian crossing
     let mut normalized_boundary = normalize_longitudes(boundary);
 
+    // Reverse all but the first point to change from the pentagon winding,
+    // so the ring still starts from the same vertex. Corners stay at
+    // multiples of `segments` either way.
+    if opts.winding != Winding::PENTAGON {
+        normalized_boundary[1..].reverse();
+    }
+
+    if opts.start == BoundaryStart::SmallestCoordinate {
+        let start = (0..normalized_boundary.len())
+            .step_by(segments)
+            .min_by(|&i, &j| {
+                let (a, b) = (normalized_boundary[i], normalized_boundary[j]);
+                a.longitude()
+                    .total_cmp(&b.longitude())
+                    .then(a.latitude().total_cmp(&b.latitude()))
+            })
+            .unwrap_or(0);
+        normalized_boundary.rotate_left(start);
+    }
+
     if opts.closed_ring {
         let first_point = normalized_boundary[0];
         normalized_boundary.push(first_point);
+    } else {
+        // Open rings have always ended at the start corner
+        normalized_boundary.rotate_left(1);
     }
-
-    // TODO: This is a patch to make the boundary CCW, but we should fix the winding order of the pentagon
-    // throughout the whole codebase
-    normalized_boundary.reverse();
     Ok(normalized_boundary)
 }
 
+/// Convert A5 cell ID to boundary coordinates for flat-map consumers.
+///
+/// Like `cell_to_boundary`, but every longitude lies within [-180, 180]: a
+/// cell crossing the antimeridian is split into one ring per side, forming
+/// a MultiPolygon, and a cell containing a pole is closed along the pole's
+/// edge of the map. Pole vertices are inserted in both cases, as RFC 7946
+/// GeoJSON and equirectangular renderers expect. Rings honour the same
+/// options, except that split rings start where the split leaves them.
+pub fn cell_to_split_boundary(
+    cell_id: u64,
+    options: Option<CellToBoundaryOptions>,
+) -> Result<Vec<Vec<LonLat>>, A5Error> {
+    if cell_id == WORLD_CELL {
+        return Ok(Vec::new());
+    }
+
+    let opts = options.unwrap_or_default();
+    // Take the closed ring and drop its last point, which keeps the start
+    // vertex of `cell_to_boundary`
+    let mut boundary = cell_to_boundary(
+        cell_id,
+        Some(CellToBoundaryOptions {
+            closed_ring: true,
+            ..opts.clone()
+        }),
+    )?;
+    boundary.pop();
+
+    let mut parts = split_antimeridian(&boundary);
+    for part in &mut parts {
+        if opts.winding == Winding::Clockwise {
+            part[1..].reverse();
+        }
+        if opts.closed_ring {
+            part.push(part[0]);
+        }
+    }
+    Ok(parts)
+}
+
 /// Test if an A5 cell contains a given point (in A5's internal spherical frame).
-pub fn a5cell_contains_point(cell: &A5Cell, spherical: Spherical) -> Result<f64, String> {
+pub fn a5cell_contains_point(cell: &A5Cell, spherical: Spherical) -> Result<f64, A5Error> {
     use crate::core::tiling::{get_face_vertices, get_quintant_vertices};
 
     let dodecahedron = DodecahedronProjection::get_thread_local();
@@ -384,7 +621,7 @@ pub fn a5cell_contains_point(cell: &A5Cell, spherical: Spherical) -> Result<f64,
 /// pentagon's straight 2D edges. The segment is treated as a 2D straight line
 /// in Face coords — accurate when the segment is short relative to the face
 /// (DSEA distortion is negligible at sub-cell scales).
-pub fn cell_intersects_segment(cell_id: u64, a: LonLat, b: LonLat) -> Result<bool, String> {
+pub fn cell_intersects_segment(cell_id: u64, a: LonLat, b: LonLat) -> Result<bool, A5Error> {
     if cell_id == WORLD_CELL {
         return Ok(true);
     }
@@ -0,0 +1,288 @@
+// A5
+// SPDX-License-Identifier: Apache-2.0
+// Copyright (c) A5 contributors
+
+//! Bounding boxes and caps of cells, for coarse filtering and spatial indexes.
+//!
+//! Cell edges unproject to slightly curved paths, so bounds taken from the
+//! corners alone can cut off the bulge of an edge. The boundary is instead
+//! sampled so that no step is longer than a small fraction of the edge, and
+//! the bounds are padded by half a step: every point of the true edge lies
+//! within that distance of a sample, so the bounds are conservative.
+
+use crate::coordinate_systems::{Cartesian, LonLat};
+use crate::core::cell::{cell_to_boundary, cell_to_split_boundary, CellToBoundaryOptions};
+use crate::core::cell_info::cell_edge_length_avg;
+use crate::core::compact::compact;
+use crate::core::constants::AUTHALIC_RADIUS_EARTH;
+use crate::core::coordinate_transforms::{from_lon_lat, to_cartesian};
+use crate::core::error::A5Error;
+use crate::core::serialization::{get_resolution, WORLD_CELL};
+use crate::geometry::prepared_polygon::BoundingCap;
+use crate::utils::great_circle::short_arc_distance;
+use crate::utils::vector::vec3_length;
+
+/// Longest sampling step along the boundary, as a fraction of the average
+/// edge length of the resolution
+const SAMPLE_FRACTION: f64 = 1.0 / 64.0;
+
+/// Allowance for a step of the edge being longer than the great circle
+/// between its ends, which it exceeds by far less than this at the
+/// sampling density above
+const PADDING_MARGIN: f64 = 1.01;
+
+/// Longitude/latitude bounding box in degrees.
+///
+/// As in RFC 7946, `west > east` means the box crosses the antimeridian and
+/// covers `[west, 180]` and `[-180, east]`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BoundingBox {
+    pub west: f64,
+    pub south: f64,
+    pub east: f64,
+    pub north: f64,
+}
+
+impl BoundingBox {
+    /// Box covering the whole globe
+    pub const WORLD: BoundingBox = BoundingBox {
+        west: -180.0,
+        south: -90.0,
+        east: 180.0,
+        north: 90.0,
+    };
+
+    /// Whether the box wraps across the antimeridian
+    pub fn crosses_antimeridian(&self) -> bool {
+        self.west > self.east
+    }
+
+    /// Whether a point lies within the box, edges included
+    pub fn contains(&self, lonlat: LonLat) -> bool {
+        let (lon, lat) = (lonlat.longitude(), lonlat.latitude());
+        if !(self.south..=self.north).contains(&lat) {
+            return false;
+        }
+        if self.crosses_antimeridian() {
+            lon >= self.west || lon <= self.east
+        } else {
+            (self.west..=self.east).contains(&lon)
+        }
+    }
+
+    /// Longitude intervals covered by the box, split at the antimeridian
+    fn longitude_intervals(&self) -> Vec<(f64, f64)> {
+        if self.crosses_antimeridian() {
+            vec![(self.west, 180.0), (-180.0, self.east)]
+        } else {
+            vec![(self.west, self.east)]
+        }
+    }
+}
+
+/// Smallest range of longitudes covering all of `intervals`, each within
+/// [-180, 180], as `(west, east)` with `west > east` if it wraps
+fn covering_longitudes(mut intervals: Vec<(f64, f64)>) -> (f64, f64) {
+    intervals.sort_by(|a, b| a.0.total_cmp(&b.0));
+    let mut merged: Vec<(f64, f64)> = Vec::with_capacity(intervals.len());
+    for (west, east) in intervals {
+        match merged.last_mut() {
+            Some(last) if west <= last.1 => last.1 = last.1.max(east),
+            _ => merged.push((west, east)),
+        }
+    }
+
+    // Leave out the widest gap between the merged intervals, starting with
+    // the one across the antimeridian
+    let n = merged.len();
+    let mut range = (merged[0].0, merged[n - 1].1);
+    let mut widest = merged[0].0 + 360.0 - merged[n - 1].1;
+    for i in 1..n {
+        let gap = merged[i].0 - merged[i - 1].1;
+        if gap > widest {
+            widest = gap;
+            range = (merged[i].0, merged[i - 1].1);
+        }
+    }
+    range
+}
+
+/// Bring a longitude back into [-180, 180] after padding
+fn rewrap(longitude: f64) -> f64 {
+    if longitude < -180.0 {
+        longitude + 360.0
+    } else if longitude > 180.0 {
+        longitude - 360.0
+    } else {
+        longitude
+    }
+}
+
+/// Grow a box by `padding` radians in every direction, widening it to all
+/// longitudes if it reaches a pole or wraps all the way around
+fn pad(bbox: BoundingBox, padding: f64) -> BoundingBox {
+    let south = (bbox.south - padding.to_degrees()).max(-90.0);
+    let north = (bbox.north + padding.to_degrees()).min(90.0);
+    if south == -90.0 || north == 90.0 {
+        return BoundingBox {
+            south,
+            north,
+            ..BoundingBox::WORLD
+        };
+    }
+
+    // A point `padding` away from latitude `φ` is at most
+    // asin(sin(padding) / cos(φ)) away in longitude
+    let latitude = south.abs().max(north.abs()).to_radians();
+    let lon_padding = (padding.sin() / latitude.cos())
+        .min(1.0)
+        .asin()
+        .to_degrees();
+    let mut width = bbox.east - bbox.west;
+    if bbox.crosses_antimeridian() {
+        width += 360.0;
+    }
+    if width + 2.0 * lon_padding >= 360.0 {
+        return BoundingBox {
+            south,
+            north,
+            ..BoundingBox::WORLD
+        };
+    }
+    BoundingBox {
+        west: rewrap(bbox.west - lon_padding),
+        south,
+        east: rewrap(bbox.east + lon_padding),
+        north,
+    }
+}
+
+/// Sampling step along the boundary of a cell, in meters
+fn sample_spacing(cell: u64) -> f64 {
+    cell_edge_length_avg(get_resolution(cell)) * SAMPLE_FRACTION
+}
+
+/// Padding covering the true edge between samples, in radians
+fn sample_padding(spacing: f64) -> f64 {
+    spacing / 2.0 * PADDING_MARGIN / AUTHALIC_RADIUS_EARTH
+}
+
+fn sample_options(spacing: f64) -> Option<CellToBoundaryOptions> {
+    Some(CellToBoundaryOptions {
+        closed_ring: false,
+        max_segment_length_m: Some(spacing),
+        ..Default::default()
+    })
+}
+
+/// Returns a longitude/latitude box containing the whole cell.
+///
+/// Cells crossing the antimeridian give a wrapped box with `west > east`,
+/// and cells containing a pole, or reaching within the padding of one, span
+/// all longitudes. The box includes the curved edges of the cell, padded by
+/// a fraction of the edge length, so it may be slightly larger than the cell.
+pub fn cell_to_bbox(cell: u64) -> Result<BoundingBox, A5Error> {
+    if cell == WORLD_CELL {
+        return Ok(BoundingBox::WORLD);
+    }
+
+    let spacing = sample_spacing(cell);
+    let parts = cell_to_split_boundary(cell, sample_options(spacing))?;
+    let mut south = f64::INFINITY;
+    let mut north = f64::NEG_INFINITY;
+    let mut intervals = Vec::with_capacity(parts.len());
+    for part in &parts {
+        let mut west = f64::INFINITY;
+        let mut east = f64::NEG_INFINITY;
+        for point in part {
+            west = west.min(point.longitude());
+            east = east.max(point.longitude());
+            south = south.min(point.latitude());
+            north = north.max(point.latitude());
+        }
+        intervals.push((west, east));
+    }
+
+    let (west, east) = covering_longitudes(intervals);
+    let bbox = BoundingBox {
+        west,
+        south,
+        east,
+        north,
+    };
+    Ok(pad(bbox, sample_padding(spacing)))
+}
+
+/// Returns a longitude/latitude box containing all of `cells`, or None if
+/// there are none.
+///
+/// The cells are compacted first, and the box is the smallest one covering
+/// the box of each cell, wrapping across the antimeridian when that is
+/// narrower (see `cell_to_bbox`).
+pub fn cells_to_bbox(cells: &[u64]) -> Result<Option<BoundingBox>, A5Error> {
+    if cells.is_empty() {
+        return Ok(None);
+    }
+    if cells.contains(&WORLD_CELL) {
+        return Ok(Some(BoundingBox::WORLD));
+    }
+
+    let mut south = f64::INFINITY;
+    let mut north = f64::NEG_INFINITY;
+    let mut intervals = Vec::new();
+    for cell in compact(cells)? {
+        let bbox = cell_to_bbox(cell)?;
+        south = south.min(bbox.south);
+        north = north.max(bbox.north);
+        intervals.extend(bbox.longitude_intervals());
+    }
+
+    let (west, east) = covering_longitudes(intervals);
+    Ok(Some(BoundingBox {
+        west,
+        south,
+        east,
+        north,
+    }))
+}
+
+/// Returns a spherical cap containing the whole cell.
+///
+/// The cap is centred on the mean of the boundary samples and, like
+/// `cell_to_bbox`, includes the curved edges of the cell. The world cell gives a cap
+/// covering the whole sphere.
+pub fn cell_to_bounding_cap(cell: u64) -> Result<BoundingCap, A5Error> {
+    if cell == WORLD_CELL {
+        return Ok(BoundingCap {
+            center: Cartesian::new(0.0, 0.0, 1.0),
+            min_dot: -1.0,
+        });
+    }
+
+    let spacing = sample_spacing(cell);
+    let samples: Vec<Cartesian> = cell_to_boundary(cell, sample_options(spacing))?
+        .into_iter()
+        .map(|point| to_cartesian(from_lon_lat(point)))
+        .collect();
+    let sum = samples
+        .iter()
+        .fold(Cartesian::new(0.0, 0.0, 0.0), |sum, v| {
+            Cartesian::new(sum.x() + v.x(), sum.y() + v.y(), sum.z() + v.z())
+        });
+    let norm = vec3_length(&sum);
+    let center = Cartesian::new(sum.x() / norm, sum.y() / norm, sum.z() / norm);
+
+    // Distances from chords, which stay accurate for the tiniest cells, and
+    // an absolute allowance for rounding in the dot product of a containment
+    // test
+    let radius = samples
+        .iter()
+        .map(|&v| short_arc_distance(center, v))
+        .fold(0.0, f64::max);
+    let angle = radius / AUTHALIC_RADIUS_EARTH + sample_padding(spacing);
+    Ok(BoundingCap {
+        center,
+        min_dot: angle.min(std::f64::consts::PI).cos() - 4.0 * f64::EPSILON,
+    })
+}
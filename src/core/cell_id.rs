@@ -0,0 +1,133 @@
+// A5
+// SPDX-License-Identifier: Apache-2.0
+// Copyright (c) A5 contributors
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::coordinate_systems::LonLat;
+use crate::core::cell::{cell_to_boundary, cell_to_lonlat, lonlat_to_cell, CellToBoundaryOptions};
+use crate::core::error::A5Error;
+use crate::core::hex::{hex_to_u64, u64_to_hex};
+use crate::core::serialization::{
+    cell_to_children, cell_to_parent, get_resolution, validate_cell, WORLD_CELL,
+};
+use crate::traversal::global_neighbors::get_global_cell_neighbors;
+
+/// A validated A5 cell identifier.
+///
+/// Wraps the raw `u64` index so cell ids cannot be confused with other
+/// integers. Construct with `CellId::try_from(u64)`, `str::parse` (hex) or
+/// `CellId::from_lonlat`; the inherent methods wrap the free functions of
+/// the public API. Ordering follows the raw index, i.e. curve order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CellId(u64);
+
+impl CellId {
+    /// The abstract cell covering the whole world (resolution -1)
+    pub const WORLD: CellId = CellId(WORLD_CELL);
+
+    /// Get the raw 64-bit cell index
+    pub const fn get(&self) -> u64 {
+        self.0
+    }
+
+    /// The cell containing `lonlat` at the given resolution
+    pub fn from_lonlat(lonlat: LonLat, resolution: i32) -> Result<CellId, A5Error> {
+        Ok(CellId(lonlat_to_cell(lonlat, resolution)?))
+    }
+
+    /// Resolution of the cell, -1 for the world cell
+    pub fn resolution(&self) -> i32 {
+        get_resolution(self.0)
+    }
+
+    /// Ancestor at `resolution`, or the direct parent when `None`
+    pub fn parent(&self, resolution: Option<i32>) -> Result<CellId, A5Error> {
+        Ok(CellId(cell_to_parent(self.0, resolution)?))
+    }
+
+    /// Descendants at `resolution`, or the direct children when `None`
+    pub fn children(&self, resolution: Option<i32>) -> Result<Vec<CellId>, A5Error> {
+        Ok(cell_to_children(self.0, resolution)?
+            .into_iter()
+            .map(CellId)
+            .collect())
+    }
+
+    /// Center of the cell
+    pub fn center(&self) -> Result<LonLat, A5Error> {
+        cell_to_lonlat(self.0)
+    }
+
+    /// Boundary of the cell, see `cell_to_boundary`
+    pub fn boundary(&self, options: Option<CellToBoundaryOptions>) -> Result<Vec<LonLat>, A5Error> {
+        cell_to_boundary(self.0, options)
+    }
+
+    /// Cells sharing an edge with this cell, none for the world cell
+    pub fn neighbors(&self) -> Vec<CellId> {
+        self.global_neighbors(true)
+    }
+
+    /// Cells sharing an edge or a vertex with this cell, none for the world cell
+    pub fn vertex_neighbors(&self) -> Vec<CellId> {
+        self.global_neighbors(false)
+    }
+
+    fn global_neighbors(&self, edge_only: bool) -> Vec<CellId> {
+        if self.0 == WORLD_CELL {
+            return Vec::new();
+        }
+        get_global_cell_neighbors(self.0, edge_only)
+            .into_iter()
+            .map(CellId)
+            .collect()
+    }
+}
+
+impl TryFrom<u64> for CellId {
+    type Error = A5Error;
+
+    /// Accepts only well-formed ids, see `validate_cell`.
+    fn try_from(index: u64) -> Result<Self, Self::Error> {
+        validate_cell(index)?;
+        Ok(CellId(index))
+    }
+}
+
+impl From<CellId> for u64 {
+    fn from(cell: CellId) -> Self {
+        cell.0
+    }
+}
+
+impl FromStr for CellId {
+    type Err = A5Error;
+
+    fn from_str(hex: &str) -> Result<Self, Self::Err> {
+        CellId::try_from(hex_to_u64(hex)?)
+    }
+}
+
+impl fmt::Display for CellId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&u64_to_hex(self.0))
+    }
+}
+
+/// Serializes as the hex string from `u64_to_hex`; deserialization validates.
+#[cfg(feature = "serde")]
+impl serde::Serialize for CellId {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&u64_to_hex(self.0))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for CellId {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let hex = String::deserialize(deserializer)?;
+        hex.parse().map_err(serde::de::Error::custom)
+    }
+}
@@ -0,0 +1,163 @@
+// A5
+// SPDX-License-Identifier: Apache-2.0
+// Copyright (c) A5 contributors
+
+//! Geometric measurements of individual cells on the authalic sphere.
+//!
+//! Cell edges are straight lines in the face plane of the dodecahedron
+//! projection, which unproject to slightly curved paths rather than great
+//! circle arcs. Each edge is therefore sampled densely before unprojecting, so
+//! the results measure the true cell shape, not its geodesic polygon.
+//!
+//! Accuracy is limited by the inverse projection, which loses precision very
+//! close to a face centre: cells touching one measure to within 10^-6 up to
+//! resolution 16, but degrade quickly above it, as `cell_to_boundary` does.
+//!
+//! The world cell has no boundary to measure, so every function here returns
+//! `A5Error::DegenerateGeometry` for it.
+
+use crate::coordinate_systems::Cartesian;
+use crate::core::cell::get_pentagon;
+use crate::core::cell_info::cell_area;
+use crate::core::constants::AUTHALIC_RADIUS_EARTH;
+use crate::core::coordinate_transforms::to_cartesian;
+use crate::core::error::A5Error;
+use crate::core::serialization::{deserialize, WORLD_CELL};
+use crate::geometry::SphericalPolygonShape;
+use crate::projections::dodecahedron::DodecahedronProjection;
+use crate::utils::great_circle::short_arc_distance;
+use crate::utils::vector::{cross, dot, length};
+
+/// Samples per edge. Treating each sample step as a great circle arc costs
+/// an error falling with the square of the step; edges are most curved on
+/// cells touching a face centre, at every resolution, and there 512 steps
+/// keep areas and lengths within one part in 10^7.
+const EDGE_SEGMENTS: usize = 512;
+
+/// Shape measurements of a single cell, see `cell_shape_metrics`
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CellShapeMetrics {
+    /// Area in square meters
+    pub area: f64,
+    /// Ratio of `area` to the nominal `cell_area` of the resolution, 1 for a
+    /// perfectly equal-area cell
+    pub area_ratio: f64,
+    /// Perimeter in meters
+    pub perimeter: f64,
+    /// Length of each edge in meters, in boundary order
+    pub edge_lengths: Vec<f64>,
+    /// Interior angle at each vertex in radians; vertex i starts edge i
+    pub interior_angles: Vec<f64>,
+    /// Isoperimetric quotient `4π · area / perimeter²`, 1 for a circle and
+    /// ~0.865 for a regular pentagon
+    pub compactness: f64,
+}
+
+/// Unprojected edges of a cell, each sampled from its start vertex to its
+/// end vertex inclusive, in the vertex order of the pentagon
+fn cell_edges(cell: u64) -> Result<Vec<Vec<Cartesian>>, A5Error> {
+    if cell == WORLD_CELL {
+        return Err(A5Error::DegenerateGeometry(
+            "the world cell has no boundary".to_string(),
+        ));
+    }
+    let cell_data = deserialize(cell)?;
+    let pentagon = get_pentagon(&cell_data)?;
+    let vertices = pentagon.get_vertices_vec().len();
+    let split_pentagon = pentagon.split_edges(EDGE_SEGMENTS);
+
+    let dodecahedron = DodecahedronProjection::get_thread_local();
+    let mut points = Vec::with_capacity(vertices * EDGE_SEGMENTS);
+    for &vertex in split_pentagon.get_vertices_vec() {
+        points.push(to_cartesian(
+            dodecahedron.inverse(vertex, cell_data.origin_id)?,
+        ));
+    }
+
+    Ok((0..vertices)
+        .map(|i| {
+            let mut edge = points[i * EDGE_SEGMENTS..(i + 1) * EDGE_SEGMENTS].to_vec();
+            edge.push(points[(i + 1) % vertices * EDGE_SEGMENTS]);
+            edge
+        })
+        .collect())
+}
+
+fn edge_length(edge: &[Cartesian]) -> f64 {
+    edge.windows(2)
+        .map(|pair| short_arc_distance(pair[0], pair[1]))
+        .sum()
+}
+
+fn area_of_edges(edges: &[Vec<Cartesian>]) -> f64 {
+    // Drop the end of each edge, which is the start of the next
+    let ring: Vec<Cartesian> = edges
+        .iter()
+        .flat_map(|edge| edge[..edge.len() - 1].iter().copied())
+        .collect();
+    SphericalPolygonShape::new(ring).get_area().get().abs()
+        * AUTHALIC_RADIUS_EARTH
+        * AUTHALIC_RADIUS_EARTH
+}
+
+/// Angle at `vertex` between the great circles towards `a` and `b`
+fn angle_between(vertex: Cartesian, a: Cartesian, b: Cartesian) -> f64 {
+    let ta = cross(vertex, a);
+    let tb = cross(vertex, b);
+    length(cross(ta, tb)).atan2(dot(ta, tb))
+}
+
+/// Interior angles between consecutive edges, using the first sample step
+/// of each edge as its direction
+fn interior_angles(edges: &[Vec<Cartesian>]) -> Vec<f64> {
+    let n = edges.len();
+    (0..n)
+        .map(|i| {
+            let incoming = &edges[(i + n - 1) % n];
+            angle_between(edges[i][0], edges[i][1], incoming[incoming.len() - 2])
+        })
+        .collect()
+}
+
+/// Returns the measured area of a cell in square meters.
+///
+/// Unlike `cell_area`, which returns the nominal area shared by all cells of
+/// a resolution, this integrates the actual cell boundary.
+pub fn cell_area_exact(cell: u64) -> Result<f64, A5Error> {
+    Ok(area_of_edges(&cell_edges(cell)?))
+}
+
+/// Returns the perimeter of a cell in meters.
+pub fn cell_perimeter(cell: u64) -> Result<f64, A5Error> {
+    Ok(cell_edge_lengths(cell)?.iter().sum())
+}
+
+/// Returns the length in meters of each edge of a cell, in boundary order.
+///
+/// Cells have 5 edges, except the triangular cells of resolution 1 which
+/// have 3.
+pub fn cell_edge_lengths(cell: u64) -> Result<Vec<f64>, A5Error> {
+    Ok(cell_edges(cell)?
+        .iter()
+        .map(|edge| edge_length(edge))
+        .collect())
+}
+
+/// Returns the area, perimeter, edge lengths, interior angles and compactness
+/// of a cell, sampling its boundary only once.
+pub fn cell_shape_metrics(cell: u64) -> Result<CellShapeMetrics, A5Error> {
+    let edges = cell_edges(cell)?;
+    let resolution = deserialize(cell)?.resolution;
+    let area = area_of_edges(&edges);
+    let edge_lengths: Vec<f64> = edges.iter().map(|edge| edge_length(edge)).collect();
+    let perimeter: f64 = edge_lengths.iter().sum();
+    Ok(CellShapeMetrics {
+        area,
+        area_ratio: area / cell_area(resolution),
+        perimeter,
+        edge_lengths,
+        interior_angles: interior_angles(&edges),
+        compactness: 4.0 * std::f64::consts::PI * area / (perimeter * perimeter),
+    })
+}
@@ -0,0 +1,313 @@
+// A5
+// SPDX-License-Identifier: Apache-2.0
+// Copyright (c) A5 contributors
+
+//! Set algebra on compacted, mixed-resolution cell sets.
+//!
+//! Every cell covers a contiguous range of "leaf keys": `quintant << 58 | S`,
+//! where S is the 58-bit Hilbert position at resolution 30. For Hilbert
+//! resolutions the range is centred on the cell id itself and is one
+//! `get_stride` wide, the same bit-prefix rule `is_child_of` relies on;
+//! resolutions 0, 1 and 30 use their own bit layouts and are mapped
+//! explicitly. Operations run on sorted, disjoint key ranges and convert back
+//! to the largest cells covering them, so nothing is ever uncompacted.
+
+use crate::core::error::A5Error;
+use crate::core::serialization::{
+    get_resolution, get_stride, validate_cell, FIRST_HILBERT_RESOLUTION, HILBERT_START_BIT,
+    MAX_RESOLUTION, WORLD_CELL,
+};
+
+/// Number of leaf keys in one quintant
+pub const QUINTANT_KEYS: u64 = 1 << HILBERT_START_BIT;
+/// One past the last leaf key: 60 quintants
+pub const END_KEY: u64 = 60 * QUINTANT_KEYS;
+
+/// Inclusive leaf-key range `[first, last]` covered by a valid cell
+pub fn cell_to_key_range(cell: u64) -> (u64, u64) {
+    let resolution = get_resolution(cell);
+    match resolution {
+        -1 => (0, END_KEY - 1),
+        0 => {
+            // Origin in the top bits, covering its 5 quintants
+            let first = 5 * (cell >> HILBERT_START_BIT) * QUINTANT_KEYS;
+            (first, first + 5 * QUINTANT_KEYS - 1)
+        }
+        MAX_RESOLUTION => {
+            let key = res30_to_key(cell);
+            (key, key)
+        }
+        _ => {
+            // Quintant and Hilbert bits followed by the marker at stride / 2
+            let stride = get_stride(resolution);
+            let first = if resolution < FIRST_HILBERT_RESOLUTION {
+                cell & !(stride - 1)
+            } else {
+                cell - stride / 2
+            };
+            (first, first + stride - 1)
+        }
+    }
+}
+
+/// Leaf key of a resolution-30 cell, undoing the variable quintant shift
+fn res30_to_key(cell: u64) -> u64 {
+    let (marker_bits, quintant_offset) = if cell & 1 != 0 {
+        (1, 0)
+    } else if cell & 0b100 != 0 {
+        (3, 32)
+    } else {
+        (5, 40)
+    };
+    let quintant_shift = HILBERT_START_BIT + marker_bits;
+    let quintant = (cell >> quintant_shift) + quintant_offset;
+    let s = (cell & ((1 << quintant_shift) - 1)) >> marker_bits;
+    quintant * QUINTANT_KEYS + s
+}
+
+/// Cell at `resolution` whose key range starts at `first`, which must be aligned.
+///
+/// Like `serialize`, resolution 30 falls back to the resolution-29 cell in
+/// quintants 42 and above.
+pub fn key_to_cell(first: u64, resolution: i32) -> u64 {
+    match resolution {
+        -1 => WORLD_CELL,
+        0 => (first / (5 * QUINTANT_KEYS)) << HILBERT_START_BIT | 1 << (HILBERT_START_BIT - 1),
+        MAX_RESOLUTION => {
+            let quintant = first / QUINTANT_KEYS;
+            let s = first % QUINTANT_KEYS;
+            let (quintant_value, marker_bits) = match quintant {
+                0..=31 => (quintant, 1),
+                32..=39 => (quintant - 32, 3),
+                40..=41 => (quintant - 40, 5),
+                _ => return key_to_cell(first & !3, MAX_RESOLUTION - 1),
+            };
+            quintant_value << (HILBERT_START_BIT + marker_bits)
+                | s << marker_bits
+                | 1 << (marker_bits - 1)
+        }
+        1 => first | 1 << (HILBERT_START_BIT - 2),
+        _ => first + get_stride(resolution) / 2,
+    }
+}
+
+/// Size and alignment of the key range of a cell at `resolution`
+pub fn block_size(resolution: i32) -> u64 {
+    match resolution {
+        -1 => END_KEY,
+        0 => 5 * QUINTANT_KEYS,
+        // Resolution-30 ids step by 2, but each is a single key
+        MAX_RESOLUTION => 1,
+        _ => get_stride(resolution),
+    }
+}
+
+/// Largest cells exactly covering the key range `[first, last]`, in key order
+fn key_range_to_cells(mut first: u64, last: u64, out: &mut Vec<u64>) {
+    while first <= last {
+        let resolution = (-1..=MAX_RESOLUTION)
+            .find(|&r| {
+                let size = block_size(r);
+                first % size == 0 && last - first >= size - 1
+            })
+            .unwrap_or(MAX_RESOLUTION);
+        out.push(key_to_cell(first, resolution));
+        first += block_size(resolution);
+    }
+}
+
+/// Sorted, disjoint, non-adjacent ranges from arbitrary inclusive ranges
+pub fn coalesce(mut ranges: Vec<(u64, u64)>) -> Vec<(u64, u64)> {
+    ranges.sort_unstable();
+    let mut out: Vec<(u64, u64)> = Vec::with_capacity(ranges.len());
+    for (first, last) in ranges {
+        match out.last_mut() {
+            Some(previous) if first <= previous.1 + 1 => previous.1 = previous.1.max(last),
+            _ => out.push((first, last)),
+        }
+    }
+    out
+}
+
+/// A set of cells stored in normalized form: sorted along the curve,
+/// without overlaps and fully compacted.
+///
+/// The set algebra works directly on this hierarchical representation, so
+/// combining coverages at fine resolutions never expands them to a common
+/// resolution. Two sets covering the same area compare equal whatever
+/// cells they were built from.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "Vec<u64>", into = "Vec<u64>"))]
+pub struct CellSet {
+    cells: Vec<u64>,
+}
+
+impl CellSet {
+    /// An empty set
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Build a set from cells at any resolutions, which may overlap.
+    ///
+    /// Returns an error if any cell id is invalid, see `validate_cell`.
+    pub fn from_cells(cells: &[u64]) -> Result<Self, A5Error> {
+        let mut ranges = Vec::with_capacity(cells.len());
+        for &cell in cells {
+            validate_cell(cell)?;
+            ranges.push(cell_to_key_range(cell));
+        }
+        Ok(Self::from_ranges(&coalesce(ranges)))
+    }
+
+    fn from_ranges(ranges: &[(u64, u64)]) -> Self {
+        let mut cells = Vec::with_capacity(ranges.len());
+        for &(first, last) in ranges {
+            key_range_to_cells(first, last, &mut cells);
+        }
+        CellSet { cells }
+    }
+
+    fn key_ranges(&self) -> Vec<(u64, u64)> {
+        coalesce(self.cells.iter().map(|&c| cell_to_key_range(c)).collect())
+    }
+
+    /// The compacted cells, in curve order
+    pub fn cells(&self) -> &[u64] {
+        &self.cells
+    }
+
+    /// Consume the set, returning its compacted cells in curve order
+    pub fn into_cells(self) -> Vec<u64> {
+        self.cells
+    }
+
+    /// Iterate over the compacted cells in curve order
+    pub fn iter(&self) -> std::slice::Iter<'_, u64> {
+        self.cells.iter()
+    }
+
+    /// Number of compacted cells (not the number of cells at any one resolution)
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Whether `cell` lies entirely within the set, i.e. it or one of its
+    /// ancestors is a member. Invalid cell ids are never contained.
+    pub fn contains(&self, cell: u64) -> bool {
+        if validate_cell(cell).is_err() {
+            return false;
+        }
+        let (first, last) = cell_to_key_range(cell);
+        // Members are disjoint and sorted, so only the last one starting at
+        // or before `first` can cover the cell
+        let i = self
+            .cells
+            .partition_point(|&member| cell_to_key_range(member).0 <= first);
+        i > 0 && cell_to_key_range(self.cells[i - 1]).1 >= last
+    }
+
+    /// Whether the two sets share any area
+    pub fn intersects(&self, other: &CellSet) -> bool {
+        let (a, b) = (self.key_ranges(), other.key_ranges());
+        let (mut i, mut j) = (0, 0);
+        while i < a.len() && j < b.len() {
+            if a[i].0 <= b[j].1 && b[j].0 <= a[i].1 {
+                return true;
+            }
+            if a[i].1 < b[j].1 {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        false
+    }
+
+    /// Cells covered by either set
+    pub fn union(&self, other: &CellSet) -> CellSet {
+        let mut ranges = self.key_ranges();
+        ranges.extend(other.key_ranges());
+        Self::from_ranges(&coalesce(ranges))
+    }
+
+    /// Cells covered by both sets
+    pub fn intersection(&self, other: &CellSet) -> CellSet {
+        let (a, b) = (self.key_ranges(), other.key_ranges());
+        let mut out = Vec::new();
+        let (mut i, mut j) = (0, 0);
+        while i < a.len() && j < b.len() {
+            let first = a[i].0.max(b[j].0);
+            let last = a[i].1.min(b[j].1);
+            if first <= last {
+                out.push((first, last));
+            }
+            if a[i].1 < b[j].1 {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        Self::from_ranges(&out)
+    }
+
+    /// Cells covered by this set but not by `other`
+    pub fn difference(&self, other: &CellSet) -> CellSet {
+        let b = other.key_ranges();
+        let mut out = Vec::new();
+        let mut j = 0;
+        for (mut first, last) in self.key_ranges() {
+            while j < b.len() && b[j].1 < first {
+                j += 1;
+            }
+            let mut k = j;
+            while k < b.len() && b[k].0 <= last {
+                if b[k].0 > first {
+                    out.push((first, b[k].0 - 1));
+                }
+                first = first.max(b[k].1.saturating_add(1));
+                if b[k].1 >= last {
+                    break;
+                }
+                k += 1;
+            }
+            if first <= last {
+                out.push((first, last));
+            }
+        }
+        Self::from_ranges(&out)
+    }
+
+    /// Cells covered by exactly one of the sets
+    pub fn symmetric_difference(&self, other: &CellSet) -> CellSet {
+        self.union(other).difference(&self.intersection(other))
+    }
+}
+
+impl TryFrom<Vec<u64>> for CellSet {
+    type Error = A5Error;
+
+    fn try_from(cells: Vec<u64>) -> Result<Self, Self::Error> {
+        CellSet::from_cells(&cells)
+    }
+}
+
+impl From<CellSet> for Vec<u64> {
+    fn from(set: CellSet) -> Self {
+        set.cells
+    }
+}
+
+impl<'a> IntoIterator for &'a CellSet {
+    type Item = &'a u64;
+    type IntoIter = std::slice::Iter<'a, u64>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.cells.iter()
+    }
+}
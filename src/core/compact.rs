@@ -10,9 +10,11 @@
 use std::collections::HashSet;
 
 use crate::core::cell_info::get_num_children;
+use crate::core::cell_set::CellSet;
+use crate::core::error::A5Error;
 use crate::core::serialization::{
-    cell_to_children, cell_to_parent, get_resolution, get_stride, is_first_child,
-    FIRST_HILBERT_RESOLUTION,
+    cell_children_iter, cell_to_children, cell_to_parent, get_resolution, get_stride,
+    is_first_child, CellChildren, FIRST_HILBERT_RESOLUTION,
 };
 
 /// Expands a set of A5 cells to a target resolution by generating all descendant cells.
@@ -29,7 +31,7 @@ use crate::core::serialization::{
 /// # Errors
 ///
 /// Returns an error if any cell is at a resolution higher than the target resolution
-pub fn uncompact(cells: &[u64], target_resolution: i32) -> Result<Vec<u64>, String> {
+pub fn uncompact(cells: &[u64], target_resolution: i32) -> Result<Vec<u64>, A5Error> {
     // First calculate how much space is needed
     let mut n = 0;
     let mut resolutions = Vec::with_capacity(cells.len());
@@ -38,10 +40,10 @@ pub fn uncompact(cells: &[u64], target_resolution: i32) -> Result<Vec<u64>, Stri
         let resolution = get_resolution(cell);
         let resolution_diff = target_resolution - resolution;
         if resolution_diff < 0 {
-            return Err(format!(
-                "Cannot uncompact cell at resolution {} to lower resolution {}",
-                resolution, target_resolution
-            ));
+            return Err(A5Error::InvalidTargetResolution {
+                target: target_resolution,
+                current: resolution,
+            });
         }
 
         resolutions.push(resolution);
@@ -66,6 +68,109 @@ pub fn uncompact(cells: &[u64], target_resolution: i32) -> Result<Vec<u64>, Stri
     Ok(result)
 }
 
+/// Lazily expands a set of A5 cells to a target resolution.
+///
+/// Yields the same cells in the same order as `uncompact`, without
+/// materializing them. Like `cell_children_iter` it supports `nth` and
+/// iteration from the back, so large expansions can be paged through.
+///
+/// # Arguments
+///
+/// * `cells` - Slice of A5 cell identifiers to uncompact
+/// * `target_resolution` - The target resolution level for all output cells
+///
+/// # Errors
+///
+/// Returns an error if any cell is at a resolution higher than the target resolution
+pub fn uncompact_iter(cells: &[u64], target_resolution: i32) -> Result<UncompactIter, A5Error> {
+    let mut children = Vec::with_capacity(cells.len());
+    let mut offsets = Vec::with_capacity(cells.len() + 1);
+    let mut n = 0;
+    offsets.push(n);
+    for &cell in cells {
+        let cell_children = cell_children_iter(cell, Some(target_resolution))?;
+        n += cell_children.len() as u64;
+        offsets.push(n);
+        children.push(cell_children);
+    }
+
+    Ok(UncompactIter {
+        children,
+        offsets,
+        front: 0,
+        back: n,
+    })
+}
+
+/// Iterator over the cells of an uncompacted set, see `uncompact_iter`.
+#[derive(Debug, Clone)]
+pub struct UncompactIter {
+    children: Vec<CellChildren>,
+    /// Position of the first descendant of each input cell, plus the total
+    offsets: Vec<u64>,
+    /// Positions of the next cell from each end, `front..back`
+    front: u64,
+    back: u64,
+}
+
+impl UncompactIter {
+    fn cell_at(&self, position: u64) -> Option<u64> {
+        let i = self.offsets.partition_point(|&offset| offset <= position) - 1;
+        self.children[i]
+            .clone()
+            .nth((position - self.offsets[i]) as usize)
+    }
+}
+
+impl Iterator for UncompactIter {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        if self.front >= self.back {
+            return None;
+        }
+        self.front += 1;
+        self.cell_at(self.front - 1)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = (self.back - self.front) as usize;
+        (len, Some(len))
+    }
+
+    fn nth(&mut self, n: usize) -> Option<u64> {
+        self.front = self.front.saturating_add(n as u64).min(self.back);
+        self.next()
+    }
+
+    fn count(self) -> usize {
+        self.len()
+    }
+
+    fn last(mut self) -> Option<u64> {
+        self.next_back()
+    }
+}
+
+impl DoubleEndedIterator for UncompactIter {
+    fn next_back(&mut self) -> Option<u64> {
+        if self.front >= self.back {
+            return None;
+        }
+        self.back -= 1;
+        self.cell_at(self.back)
+    }
+
+    fn nth_back(&mut self, n: usize) -> Option<u64> {
+        self.back = self.back.saturating_sub(n as u64).max(self.front);
+        self.next_back()
+    }
+}
+
+impl ExactSizeIterator for UncompactIter {}
+
+impl std::iter::FusedIterator for UncompactIter {}
+
 /// Compacts a set of A5 cells by replacing complete groups of sibling cells with their parent cells.
 ///
 /// # Arguments
@@ -75,7 +180,7 @@ pub fn uncompact(cells: &[u64], target_resolution: i32) -> Result<Vec<u64>, Stri
 /// # Returns
 ///
 /// Vector of compacted cell identifiers (typically smaller than input)
-pub fn compact(cells: &[u64]) -> Result<Vec<u64>, String> {
+pub fn compact(cells: &[u64]) -> Result<Vec<u64>, A5Error> {
     if cells.is_empty() {
         return Ok(Vec::new());
     }
@@ -153,3 +258,23 @@ pub fn compact(cells: &[u64]) -> Result<Vec<u64>, String> {
 
     Ok(current_cells)
 }
+
+/// Merges cell sets at mixed resolutions into a single compacted set.
+///
+/// Unlike `compact`, the input may overlap: cells that have an ancestor in
+/// the input are absorbed by it, so the union of several compacted results
+/// (e.g. from `polygon_to_cells`) can be passed directly. This is the set
+/// built by `CellSet::from_cells`, sorted by id like the output of `compact`.
+///
+/// # Arguments
+///
+/// * `cells` - Slice of A5 cell identifiers at any resolutions
+///
+/// # Returns
+///
+/// Sorted, compacted vector covering the union of the input cells
+pub fn compact_union(cells: &[u64]) -> Result<Vec<u64>, A5Error> {
+    let mut cells = CellSet::from_cells(cells)?.into_cells();
+    cells.sort_unstable();
+    Ok(cells)
+}
@@ -0,0 +1,157 @@
+// A5
+// SPDX-License-Identifier: Apache-2.0
+// Copyright (c) A5 contributors
+
+use std::fmt;
+
+/// Error type returned by the A5 public API.
+///
+/// Each variant identifies a distinct failure cause so callers can match on
+/// it directly. The `Display` output is a human-readable message intended for
+/// logs, not for parsing. New variants may be added in minor releases, so
+/// matches need a wildcard arm.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum A5Error {
+    /// The value is not a well-formed A5 cell id
+    InvalidCell {
+        cell: u64,
+        reason: InvalidCellReason,
+    },
+    /// A resolution lies outside the supported range [-1, MAX_RESOLUTION]
+    InvalidResolution(i32),
+    /// A target resolution points the wrong way in the hierarchy relative to
+    /// the resolution of the cell it is applied to
+    InvalidTargetResolution { target: i32, current: i32 },
+    /// The Hilbert curve position `s` does not fit in the given resolution
+    InvalidS { s: u64, resolution: i32 },
+    /// Expanding a cell would span too many resolution levels to enumerate
+    ResolutionDifferenceTooLarge { from: i32, to: i32 },
+    /// A child position is past the last of the parent's `count` descendants
+    InvalidChildPosition { position: u64, count: u64 },
+    /// An ordinal does not number a cell at the given resolution
+    InvalidOrdinal { ordinal: u64, resolution: i32 },
+    /// The value is not a well-formed directed edge id
+    InvalidEdge(u64),
+    /// The value is not the canonical id of a cell vertex
+    InvalidVertex(u64),
+    /// The leaves of the cell span several resolution-30 id encodings, so no
+    /// single id range holds them
+    SplitLeafRange(u64),
+    /// The two cells do not share an edge
+    NotNeighbors { origin: u64, destination: u64 },
+    /// No chain of edge-sharing cells joins the two cells
+    NoPath { start: u64, end: u64 },
+    /// The two cells are too far apart to search for a path between them,
+    /// see `MAX_GRID_DISTANCE`
+    PathSearchLimit { start: u64, end: u64 },
+    /// Two cells that must share a resolution do not
+    ResolutionMismatch { start: i32, end: i32 },
+    /// A tolerance is not a positive, finite number
+    InvalidTolerance(f64),
+    /// The string is not a valid hexadecimal cell id
+    InvalidHex(String),
+    /// Input geometry is degenerate (e.g. wrong number of vertices)
+    DegenerateGeometry(String),
+    /// Input geometry could not be parsed (e.g. malformed GeoJSON or WKT)
+    InvalidGeometry(String),
+    /// A projection between the sphere and a dodecahedron face failed
+    Projection(String),
+}
+
+impl fmt::Display for A5Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            A5Error::InvalidCell { cell, reason } => {
+                write!(f, "Invalid cell id {:x}: {}", cell, reason)
+            }
+            A5Error::InvalidResolution(resolution) => {
+                write!(f, "Resolution ({}) is out of range", resolution)
+            }
+            A5Error::InvalidTargetResolution { target, current } => write!(
+                f,
+                "Target resolution ({}) is not reachable from current resolution ({})",
+                target, current
+            ),
+            A5Error::InvalidS { s, resolution } => write!(
+                f,
+                "S ({}) is too large for resolution level {}",
+                s, resolution
+            ),
+            A5Error::ResolutionDifferenceTooLarge { from, to } => {
+                write!(f, "Resolution difference too large ({} to {})", from, to)
+            }
+            A5Error::InvalidChildPosition { position, count } => write!(
+                f,
+                "Child position ({}) is out of range for {} children",
+                position, count
+            ),
+            A5Error::InvalidOrdinal {
+                ordinal,
+                resolution,
+            } => write!(
+                f,
+                "Ordinal ({}) does not identify a cell at resolution {}",
+                ordinal, resolution
+            ),
+            A5Error::InvalidEdge(edge) => write!(f, "Invalid edge id {:x}", edge),
+            A5Error::InvalidVertex(vertex) => write!(f, "Invalid vertex id {:x}", vertex),
+            A5Error::SplitLeafRange(cell) => write!(
+                f,
+                "Leaves of cell {:x} span several id ranges, see cells_to_ranges",
+                cell
+            ),
+            A5Error::NotNeighbors {
+                origin,
+                destination,
+            } => write!(
+                f,
+                "Cells {:x} and {:x} do not share an edge",
+                origin, destination
+            ),
+            A5Error::NoPath { start, end } => {
+                write!(f, "No path between cells {:x} and {:x}", start, end)
+            }
+            A5Error::PathSearchLimit { start, end } => write!(
+                f,
+                "Cells {:x} and {:x} are too far apart to search for a path",
+                start, end
+            ),
+            A5Error::ResolutionMismatch { start, end } => write!(
+                f,
+                "Start resolution ({}) does not match end resolution ({})",
+                start, end
+            ),
+            A5Error::InvalidTolerance(tolerance) => {
+                write!(f, "Tolerance ({}) must be a positive number", tolerance)
+            }
+            A5Error::InvalidHex(hex) => write!(f, "Invalid hex string: {:?}", hex),
+            A5Error::DegenerateGeometry(message) => write!(f, "Degenerate geometry: {}", message),
+            A5Error::InvalidGeometry(message) => write!(f, "Invalid geometry: {}", message),
+            A5Error::Projection(message) => write!(f, "Projection failed: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for A5Error {}
+
+/// Why a `u64` failed cell validation, see `validate_cell`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InvalidCellReason {
+    /// The lowest set bit is not at a position used as a resolution marker
+    ResolutionMarker,
+    /// The origin (res 0) or quintant (res 1+) bits are out of range
+    Origin,
+    /// Bits outside the origin, Hilbert and marker fields are set
+    UnusedBits,
+}
+
+impl fmt::Display for InvalidCellReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            InvalidCellReason::ResolutionMarker => "no valid resolution marker",
+            InvalidCellReason::Origin => "origin out of range",
+            InvalidCellReason::UnusedBits => "unused bits are set",
+        })
+    }
+}
@@ -2,6 +2,8 @@
 // SPDX-License-Identifier: Apache-2.0
 // Copyright (c) A5 contributors
 
+use crate::core::error::A5Error;
+
 /// Converts a hexadecimal string to a u64
 ///
 /// # Arguments
@@ -11,8 +13,8 @@
 /// # Returns
 ///
 /// A u64 representing the hexadecimal value
-pub fn hex_to_u64(hex: &str) -> Result<u64, String> {
-    u64::from_str_radix(hex, 16).map_err(|e| format!("Invalid hex string: {}", e))
+pub fn hex_to_u64(hex: &str) -> Result<u64, A5Error> {
+    u64::from_str_radix(hex, 16).map_err(|_| A5Error::InvalidHex(hex.to_string()))
 }
 
 /// Converts a u64 to a hexadecimal string
@@ -0,0 +1,114 @@
+// A5
+// SPDX-License-Identifier: Apache-2.0
+// Copyright (c) A5 contributors
+
+//! Resolution-30 id ranges for indexed range scans over leaf-keyed tables.
+//!
+//! The leaves of a cell are consecutive along the Hilbert curve, and within
+//! one res-30 quintant encoding (quintants 0-31, 32-39 and 40-41, plus the
+//! res-29 fallback for 42 and above) consecutive leaves have ids a fixed
+//! step apart. A cell within one encoding therefore maps to a single
+//! `LeafRange`; only the world cell and the two origins whose quintants
+//! cross an encoding boundary need several. Because the encodings
+//! interleave, the ids between the ends of a range include leaves of other
+//! quintants; those always differ in the trailing marker bits, which the
+//! range's `mask` picks out.
+
+use crate::core::cell_set::{cell_to_key_range, coalesce, key_to_cell, QUINTANT_KEYS};
+use crate::core::error::A5Error;
+use crate::core::serialization::{validate_cell, MAX_RESOLUTION};
+
+/// First quintants of the res-30 encodings: 5-bit, 3-bit, 1-bit quintant
+/// fields, then the res-29 fallback
+const ENCODING_STARTS: [u64; 3] = [32, 40, 42];
+
+/// Inclusive range of the resolution-30 ids within one res-30 encoding.
+///
+/// The leaves in the range are exactly the ids `key` with
+/// `min <= key && key <= max && key & mask == min & mask`, as in
+/// `LeafRange::contains`. In SQL this is
+/// `key BETWEEN min AND max AND key & mask = min & mask`: the bounds alone
+/// also match leaves of quintants with another encoding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LeafRange {
+    /// First leaf id of the range
+    pub min: u64,
+    /// Last leaf id of the range
+    pub max: u64,
+    /// Trailing bits of `min` that every leaf in the range shares
+    pub mask: u64,
+}
+
+impl LeafRange {
+    fn new(min: u64, max: u64) -> LeafRange {
+        // The lowest set bit is the encoding marker, and the bits below it
+        // are zero
+        let mask = (min & min.wrapping_neg()) * 2 - 1;
+        LeafRange { min, max, mask }
+    }
+
+    /// Whether `leaf` is one of the leaves in the range
+    pub fn contains(&self, leaf: u64) -> bool {
+        (self.min..=self.max).contains(&leaf) && leaf & self.mask == self.min & self.mask
+    }
+}
+
+/// Resolution-30 id of the leaf with the given key
+fn key_to_leaf(key: u64) -> u64 {
+    key_to_cell(key, MAX_RESOLUTION)
+}
+
+/// Split a key range at encoding boundaries and convert it to leaf ids
+fn push_leaf_ranges(mut first: u64, last: u64, out: &mut Vec<LeafRange>) {
+    for start in ENCODING_STARTS.map(|q| q * QUINTANT_KEYS) {
+        if first < start && last >= start {
+            out.push(LeafRange::new(key_to_leaf(first), key_to_leaf(start - 1)));
+            first = start;
+        }
+    }
+    out.push(LeafRange::new(key_to_leaf(first), key_to_leaf(last)));
+}
+
+/// Range of the resolution-30 ids within a cell.
+///
+/// The leaves of the cell are exactly the ids the range contains, see
+/// `LeafRange` for the filter to apply when scanning an index with it.
+///
+/// Quintants 42 and above have no resolution-30 ids, so their leaves are the
+/// resolution-29 ids that `lonlat_to_cell` returns there.
+///
+/// Returns `A5Error::SplitLeafRange` for the world cell and for the
+/// resolution-0 cells whose quintants cross an encoding boundary, origins 6
+/// (quintants 30-34) and 8 (40-44); use `cells_to_ranges` for those.
+pub fn cell_to_leaf_range(cell: u64) -> Result<LeafRange, A5Error> {
+    validate_cell(cell)?;
+    let (first, last) = cell_to_key_range(cell);
+    let mut ranges = Vec::with_capacity(1);
+    push_leaf_ranges(first, last, &mut ranges);
+    match ranges[..] {
+        [range] => Ok(range),
+        _ => Err(A5Error::SplitLeafRange(cell)),
+    }
+}
+
+/// Resolution-30 id ranges covering a set of cells, see `cell_to_leaf_range`.
+///
+/// The cells may be at mixed resolutions and overlap. Ranges of cells that
+/// follow each other along the Hilbert curve are merged, so a compacted
+/// coverage needs only a handful of ranges. They are returned in curve
+/// order, split where the res-30 encoding changes: the bounds of ranges of
+/// different encodings may overlap, though never the leaves they contain.
+pub fn cells_to_ranges(cells: &[u64]) -> Result<Vec<LeafRange>, A5Error> {
+    let mut key_ranges = Vec::with_capacity(cells.len());
+    for &cell in cells {
+        validate_cell(cell)?;
+        key_ranges.push(cell_to_key_range(cell));
+    }
+
+    let mut ranges = Vec::new();
+    for (first, last) in coalesce(key_ranges) {
+        push_leaf_ranges(first, last, &mut ranges);
+    }
+    Ok(ranges)
+}
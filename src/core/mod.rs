@@ -3,14 +3,21 @@
 // Copyright (c) A5 contributors
 
 pub mod cell;
+pub mod cell_bounds;
+pub mod cell_id;
 pub mod cell_info;
+pub mod cell_metrics;
+pub mod cell_set;
 pub mod compact;
 pub mod constants;
 pub mod coordinate_transforms;
 pub mod dodecahedron_quaternions;
+pub mod error;
 pub mod face_adjacency;
 pub mod hex;
 pub mod hilbert;
+pub mod leaf_range;
+pub mod ordinal;
 pub mod origin;
 pub mod pentagon;
 pub mod serialization;
@@ -0,0 +1,169 @@
+// A5
+// SPDX-License-Identifier: Apache-2.0
+// Copyright (c) A5 contributors
+
+//! Dense numbering of the cells of one resolution, for array-backed storage.
+//!
+//! Ordinals follow the curve, which for resolution 1 and above is ascending
+//! cell id order: at resolution 0 the ordinal is the origin, above it the
+//! quintant followed by the Hilbert position.
+
+use crate::core::cell_set::{block_size, cell_to_key_range, key_to_cell, END_KEY, QUINTANT_KEYS};
+use crate::core::error::A5Error;
+use crate::core::serialization::{get_resolution, validate_cell, MAX_RESOLUTION};
+
+/// Number of cells at `resolution`, exact unlike `get_num_cells` at 28 and above
+fn num_cells(resolution: i32) -> u64 {
+    END_KEY / block_size(resolution)
+}
+
+/// Position of a cell within all cells of its resolution, in curve order.
+///
+/// Ordinals run from 0 to `get_num_cells(resolution) - 1`. At resolution 30
+/// quintants 42 and above have no cells of their own (their ids are
+/// resolution-29 cells, see `serialize`), so their ordinals are unused.
+pub fn cell_to_ordinal(cell: u64) -> Result<u64, A5Error> {
+    validate_cell(cell)?;
+    let (first, _) = cell_to_key_range(cell);
+    Ok(first / block_size(get_resolution(cell)))
+}
+
+/// Cell at `resolution` with the given ordinal, the inverse of `cell_to_ordinal`.
+///
+/// Returns `A5Error::InvalidOrdinal` for ordinals past the last cell, or
+/// that fall among the missing resolution-30 cells of quintants 42 and up.
+pub fn ordinal_to_cell(ordinal: u64, resolution: i32) -> Result<u64, A5Error> {
+    if !(-1..=MAX_RESOLUTION).contains(&resolution) {
+        return Err(A5Error::InvalidResolution(resolution));
+    }
+    let key = ordinal
+        .checked_mul(block_size(resolution))
+        .unwrap_or(END_KEY);
+    if key >= END_KEY || (resolution == MAX_RESOLUTION && key >= 42 * QUINTANT_KEYS) {
+        return Err(A5Error::InvalidOrdinal {
+            ordinal,
+            resolution,
+        });
+    }
+    Ok(key_to_cell(key, resolution))
+}
+
+/// One value for every cell of a resolution, stored in a dense array
+/// indexed by `cell_to_ordinal`.
+///
+/// Compared to a `HashMap<u64, T>` there are no keys or buckets to store, so
+/// a full resolution costs exactly `size_of::<T>()` per cell.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CellGrid<T> {
+    resolution: i32,
+    values: Vec<T>,
+}
+
+impl<T: Clone> CellGrid<T> {
+    /// A grid over all cells at `resolution`, each holding `value`.
+    ///
+    /// Resolution 30 cannot be held in memory and returns
+    /// `A5Error::InvalidResolution`, as does a level too large to address.
+    pub fn new(resolution: i32, value: T) -> Result<Self, A5Error> {
+        let len = Self::checked_len(resolution)?;
+        Ok(CellGrid {
+            resolution,
+            values: vec![value; len],
+        })
+    }
+}
+
+impl<T> CellGrid<T> {
+    fn checked_len(resolution: i32) -> Result<usize, A5Error> {
+        if !(-1..MAX_RESOLUTION).contains(&resolution) {
+            return Err(A5Error::InvalidResolution(resolution));
+        }
+        usize::try_from(num_cells(resolution)).map_err(|_| A5Error::InvalidResolution(resolution))
+    }
+
+    /// A grid over all cells at `resolution`, with values computed from each
+    /// cell id in curve order. Resolutions are limited as in `new`.
+    pub fn from_fn(resolution: i32, mut f: impl FnMut(u64) -> T) -> Result<Self, A5Error> {
+        let len = Self::checked_len(resolution)?;
+        let block = block_size(resolution);
+        let values = (0..len as u64)
+            .map(|ordinal| f(key_to_cell(ordinal * block, resolution)))
+            .collect();
+        Ok(CellGrid { resolution, values })
+    }
+
+    pub fn resolution(&self) -> i32 {
+        self.resolution
+    }
+
+    /// Number of cells at the grid resolution
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    fn index(&self, cell: u64) -> Option<usize> {
+        if validate_cell(cell).is_err() || get_resolution(cell) != self.resolution {
+            return None;
+        }
+        let (first, _) = cell_to_key_range(cell);
+        Some((first / block_size(self.resolution)) as usize)
+    }
+
+    /// Value of a cell, or `None` for invalid ids and cells at another resolution
+    pub fn get(&self, cell: u64) -> Option<&T> {
+        self.index(cell).map(|i| &self.values[i])
+    }
+
+    pub fn get_mut(&mut self, cell: u64) -> Option<&mut T> {
+        self.index(cell).map(|i| &mut self.values[i])
+    }
+
+    /// Replace the value of a cell, returning the previous one.
+    ///
+    /// Returns `A5Error::InvalidResolution` if the cell is at a different
+    /// resolution from the grid.
+    pub fn set(&mut self, cell: u64, value: T) -> Result<T, A5Error> {
+        validate_cell(cell)?;
+        match self.get_mut(cell) {
+            Some(slot) => Ok(std::mem::replace(slot, value)),
+            None => Err(A5Error::InvalidResolution(get_resolution(cell))),
+        }
+    }
+
+    /// Cells and their values, in curve order
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = (u64, &T)> + ExactSizeIterator {
+        let (resolution, block) = (self.resolution, block_size(self.resolution));
+        self.values
+            .iter()
+            .enumerate()
+            .map(move |(i, value)| (key_to_cell(i as u64 * block, resolution), value))
+    }
+
+    /// Cells and mutable references to their values, in curve order
+    pub fn iter_mut(
+        &mut self,
+    ) -> impl DoubleEndedIterator<Item = (u64, &mut T)> + ExactSizeIterator {
+        let (resolution, block) = (self.resolution, block_size(self.resolution));
+        self.values
+            .iter_mut()
+            .enumerate()
+            .map(move |(i, value)| (key_to_cell(i as u64 * block, resolution), value))
+    }
+
+    /// Values indexed by ordinal, see `cell_to_ordinal`
+    pub fn values(&self) -> &[T] {
+        &self.values
+    }
+
+    pub fn values_mut(&mut self) -> &mut [T] {
+        &mut self.values
+    }
+
+    pub fn into_values(self) -> Vec<T> {
+        self.values
+    }
+}
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 // Copyright (c) A5 contributors
 
+use crate::core::error::{A5Error, InvalidCellReason};
 use crate::core::origin::get_origins;
 use crate::core::utils::{A5Cell, OriginId};
 
@@ -46,7 +47,7 @@ pub fn get_resolution(index: u64) -> i32 {
     resolution
 }
 
-pub fn deserialize(index: u64) -> Result<A5Cell, String> {
+pub fn deserialize(index: u64) -> Result<A5Cell, A5Error> {
     let resolution = get_resolution(index);
 
     // Technically not a resolution, but can be useful to think of as an
@@ -89,13 +90,19 @@ pub fn deserialize(index: u64) -> Result<A5Cell, String> {
     let origins = get_origins();
     let (origin_id, segment) = if resolution == 0 {
         if top_bits >= origins.len() {
-            return Err(format!("Could not parse origin: {}", top_bits));
+            return Err(A5Error::InvalidCell {
+                cell: index,
+                reason: InvalidCellReason::Origin,
+            });
         }
         (top_bits as OriginId, 0)
     } else {
         let origin_id = top_bits / 5;
         if origin_id >= origins.len() {
-            return Err(format!("Could not parse origin: {}", top_bits));
+            return Err(A5Error::InvalidCell {
+                cell: index,
+                reason: InvalidCellReason::Origin,
+            });
         }
         let origin = &origins[origin_id];
         let segment = (top_bits + origin.first_quintant) % 5;
@@ -125,7 +132,7 @@ pub fn deserialize(index: u64) -> Result<A5Cell, String> {
     })
 }
 
-pub fn serialize(cell: &A5Cell) -> Result<u64, String> {
+pub fn serialize(cell: &A5Cell) -> Result<u64, A5Error> {
     let A5Cell {
         origin_id,
         segment,
@@ -134,7 +141,7 @@ pub fn serialize(cell: &A5Cell) -> Result<u64, String> {
     } = cell;
 
     if *resolution > MAX_RESOLUTION {
-        return Err(format!("Resolution ({}) is too large", resolution));
+        return Err(A5Error::InvalidResolution(*resolution));
     }
 
     if *resolution == -1 {
@@ -194,10 +201,10 @@ pub fn serialize(cell: &A5Cell) -> Result<u64, String> {
         let hilbert_bits = 2 * hilbert_levels as u32;
         let max_s = 1u64 << hilbert_bits;
         if *s >= max_s {
-            return Err(format!(
-                "S ({}) is too large for resolution level {}",
-                s, resolution
-            ));
+            return Err(A5Error::InvalidS {
+                s: *s,
+                resolution: *resolution,
+            });
         }
         index += *s << (quintant_shift - hilbert_bits);
     }
@@ -208,79 +215,285 @@ pub fn serialize(cell: &A5Cell) -> Result<u64, String> {
     Ok(index)
 }
 
-pub fn cell_to_children(index: u64, child_resolution: Option<i32>) -> Result<Vec<u64>, String> {
-    let cell = deserialize(index)?;
+pub fn cell_to_children(index: u64, child_resolution: Option<i32>) -> Result<Vec<u64>, A5Error> {
+    let children = cell_children_iter(index, child_resolution)?;
+    if children.resolution - children.parent_resolution.max(FIRST_HILBERT_RESOLUTION - 1) > 20 {
+        // Prevent overflow
+        return Err(A5Error::ResolutionDifferenceTooLarge {
+            from: children.parent_resolution,
+            to: children.resolution,
+        });
+    }
+    Ok(children.collect())
+}
+
+/// Lazily iterate over the descendants of a cell at `child_resolution`
+/// (default: the next resolution), in the same order as `cell_to_children`.
+///
+/// Nothing is allocated, so arbitrarily deep levels can be walked or paged
+/// through with `nth`, `skip` and `rev`. The iterator knows its exact length,
+/// which matches `get_num_children`.
+pub fn cell_children_iter(
+    index: u64,
+    child_resolution: Option<i32>,
+) -> Result<CellChildren, A5Error> {
     let A5Cell {
         origin_id,
         segment,
         s,
         resolution: current_resolution,
-    } = cell;
+    } = deserialize(index)?;
     let new_resolution = child_resolution.unwrap_or(current_resolution + 1);
 
     if new_resolution < current_resolution {
-        return Err(format!(
-            "Target resolution ({}) must be equal to or greater than current resolution ({})",
-            new_resolution, current_resolution
-        ));
+        return Err(A5Error::InvalidTargetResolution {
+            target: new_resolution,
+            current: current_resolution,
+        });
     }
 
     if new_resolution > MAX_RESOLUTION {
-        return Err(format!(
-            "Target resolution ({}) exceeds maximum resolution ({})",
-            new_resolution, MAX_RESOLUTION
-        ));
+        return Err(A5Error::InvalidResolution(new_resolution));
     }
 
-    // If target resolution equals current resolution, return the original cell
-    if new_resolution == current_resolution {
-        return Ok(vec![index]);
-    }
+    // The world fans out to the 12 origins, and each origin to its 5 segments
+    let origins = if current_resolution == -1 && new_resolution > -1 {
+        12
+    } else {
+        1
+    };
+    let segments = if current_resolution <= 0 && new_resolution > 0 {
+        None
+    } else {
+        Some(segment)
+    };
 
-    let mut new_origin_ids = vec![origin_id];
-    let mut new_segments = vec![segment];
+    // Below the first Hilbert level each resolution splits every cell in 4
+    let resolution_diff =
+        (new_resolution - current_resolution.max(FIRST_HILBERT_RESOLUTION - 1)).max(0) as u32;
+    let per_segment = 1u64 << (2 * resolution_diff);
+    let len = origins * if segments.is_some() { 1 } else { 5 } * per_segment;
 
-    if current_resolution == -1 {
-        new_origin_ids = (0..12).collect();
-    }
+    Ok(CellChildren {
+        origin_id,
+        segment: segments,
+        s: s << (2 * resolution_diff),
+        per_segment,
+        resolution: new_resolution,
+        parent_resolution: current_resolution,
+        front: 0,
+        back: len,
+    })
+}
 
-    if (current_resolution == -1 && new_resolution > 0) || current_resolution == 0 {
-        new_segments = vec![0, 1, 2, 3, 4];
+/// Position of `index` among the descendants of its ancestor at
+/// `parent_resolution`, in the order produced by `cell_to_children`.
+///
+/// Positions run from 0 to `get_num_children(parent_resolution, resolution) - 1`,
+/// including the fan-out of 12 origins below the world cell and 5 quintants
+/// below each origin.
+pub fn cell_to_child_pos(index: u64, parent_resolution: i32) -> Result<u64, A5Error> {
+    validate_cell(index)?;
+    let A5Cell {
+        origin_id,
+        segment,
+        s,
+        resolution,
+    } = deserialize(index)?;
+    if parent_resolution < -1 {
+        return Err(A5Error::InvalidResolution(parent_resolution));
+    }
+    if parent_resolution > resolution {
+        return Err(A5Error::InvalidTargetResolution {
+            target: parent_resolution,
+            current: resolution,
+        });
     }
 
-    let resolution_diff =
-        new_resolution - std::cmp::max(current_resolution, FIRST_HILBERT_RESOLUTION - 1);
-    let children_count = if resolution_diff <= 0 {
-        1
-    } else if resolution_diff > 20 {
-        // Prevent overflow
-        return Err("Resolution difference too large".to_string());
+    // Mirrors the layout of `cell_children_iter`: origin, then segment, then S
+    let origin_offset = if parent_resolution == -1 && resolution > -1 {
+        origin_id as u64
     } else {
-        4_usize.pow(resolution_diff as u32)
+        0
     };
-    let mut children = Vec::new();
-    let shifted_s = if resolution_diff > 0 {
-        s << (2 * resolution_diff)
+    let (segments, segment_offset) = if parent_resolution <= 0 && resolution > 0 {
+        (5, segment as u64)
     } else {
-        s
+        (1, 0)
     };
+    let resolution_diff =
+        (resolution - parent_resolution.max(FIRST_HILBERT_RESOLUTION - 1)).max(0) as u32;
+    let per_segment = 1u64 << (2 * resolution_diff);
+    Ok((origin_offset * segments + segment_offset) * per_segment + (s & (per_segment - 1)))
+}
 
-    for &new_origin_id in &new_origin_ids {
-        for &new_segment in &new_segments {
-            for i in 0..children_count {
-                let new_s = shifted_s + i as u64;
-                let new_cell = A5Cell {
-                    origin_id: new_origin_id,
-                    segment: new_segment,
-                    s: new_s,
-                    resolution: new_resolution,
-                };
-                children.push(serialize(&new_cell)?);
+/// Descendant of `parent` at `child_resolution` with the given position, the
+/// inverse of `cell_to_child_pos`.
+///
+/// Returns `A5Error::InvalidChildPosition` if `position` is not below
+/// `get_num_children` for the two resolutions. Resolution-30 children in
+/// quintants 42 and above fall back to resolution 29 as in `serialize`, so
+/// there four consecutive positions give the same id.
+pub fn child_pos_to_cell(
+    position: u64,
+    parent: u64,
+    child_resolution: i32,
+) -> Result<u64, A5Error> {
+    validate_cell(parent)?;
+    let children = cell_children_iter(parent, Some(child_resolution))?;
+    let count = children.back;
+    if position >= count {
+        return Err(A5Error::InvalidChildPosition { position, count });
+    }
+    Ok(children.child_at(position))
+}
+
+/// Iterator over the children of a cell, see `cell_children_iter`.
+#[derive(Debug, Clone)]
+pub struct CellChildren {
+    /// First origin of the children: the cell's own, or 0 below the world cell
+    origin_id: OriginId,
+    /// The cell's segment, or `None` when the children span all 5 segments
+    segment: Option<usize>,
+    /// Hilbert position of the first child within each segment
+    s: u64,
+    per_segment: u64,
+    resolution: i32,
+    parent_resolution: i32,
+    /// Positions of the next child from each end, `front..back`
+    front: u64,
+    back: u64,
+}
+
+impl CellChildren {
+    fn child_at(&self, position: u64) -> u64 {
+        let block = position / self.per_segment;
+        let (origin_offset, segment) = match self.segment {
+            Some(segment) => (block, segment),
+            None => (block / 5, (block % 5) as usize),
+        };
+        let cell = A5Cell {
+            origin_id: self.origin_id + origin_offset as OriginId,
+            segment,
+            s: self.s + position % self.per_segment,
+            resolution: self.resolution,
+        };
+        // Origin, segment and S are all in range by construction
+        serialize(&cell).expect("child of a valid cell")
+    }
+}
+
+impl Iterator for CellChildren {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        if self.front >= self.back {
+            return None;
+        }
+        self.front += 1;
+        Some(self.child_at(self.front - 1))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = (self.back - self.front) as usize;
+        (len, Some(len))
+    }
+
+    fn nth(&mut self, n: usize) -> Option<u64> {
+        self.front = self.front.saturating_add(n as u64).min(self.back);
+        self.next()
+    }
+
+    fn count(self) -> usize {
+        self.len()
+    }
+
+    fn last(mut self) -> Option<u64> {
+        self.next_back()
+    }
+}
+
+impl DoubleEndedIterator for CellChildren {
+    fn next_back(&mut self) -> Option<u64> {
+        if self.front >= self.back {
+            return None;
+        }
+        self.back -= 1;
+        Some(self.child_at(self.back))
+    }
+
+    fn nth_back(&mut self, n: usize) -> Option<u64> {
+        self.back = self.back.saturating_sub(n as u64).max(self.front);
+        self.next_back()
+    }
+}
+
+impl ExactSizeIterator for CellChildren {}
+
+impl std::iter::FusedIterator for CellChildren {}
+
+/// Validate that `index` is a well-formed A5 cell id, reporting why it is not.
+///
+/// Checks, in order:
+/// - the resolution marker: res-30 ids end in one of the quintant marker
+///   patterns `...1`, `...100` or `...10000`; every other id must have its
+///   lowest set bit at a Hilbert marker position (bits 1, 3, .., 55) or at the
+///   res 1 / res 0 marker (bits 56 / 57). This also guarantees that all bits
+///   below the marker are zero.
+/// - the origin / quintant range: < 12 origins at res 0, < 60 quintants above.
+/// - unused bits: res 1 has a single bit (57) between quintant and marker.
+///
+/// The Hilbert field is exactly 2 bits per level (58 bits at res 30, where the
+/// narrower quintant field makes room), so every S it can hold is in range.
+pub fn validate_cell(index: u64) -> Result<(), A5Error> {
+    let invalid = |reason| A5Error::InvalidCell {
+        cell: index,
+        reason,
+    };
+
+    if index == WORLD_CELL {
+        return Ok(());
+    }
+
+    // Res 30: quintant is 0-31, 32-39 or 40-41 depending on the marker, so the
+    // origin is always in range and there are no unused bits.
+    if is_max_resolution(index) {
+        return Ok(());
+    }
+
+    let marker = index.trailing_zeros();
+    let quintant = index >> HILBERT_START_BIT;
+    match marker {
+        // res 0: 6-bit origin id above the marker
+        57 => {
+            if quintant >= 12 {
+                return Err(invalid(InvalidCellReason::Origin));
+            }
+        }
+        // res 1: 6-bit quintant, bit 57 unused
+        56 => {
+            if quintant >= 60 {
+                return Err(invalid(InvalidCellReason::Origin));
+            }
+            if index & (1u64 << 57) != 0 {
+                return Err(invalid(InvalidCellReason::UnusedBits));
             }
         }
+        // res 2..29: marker at bit 59 - 2 * resolution
+        1..=55 if marker % 2 == 1 => {
+            if quintant >= 60 {
+                return Err(invalid(InvalidCellReason::Origin));
+            }
+        }
+        _ => return Err(invalid(InvalidCellReason::ResolutionMarker)),
     }
 
-    Ok(children)
+    Ok(())
+}
+
+/// Whether `index` is a well-formed A5 cell id, see `validate_cell`.
+pub fn is_valid_cell(index: u64) -> bool {
+    validate_cell(index).is_ok()
 }
 
 /// Cheap predicate that mirrors the first three checks in `get_resolution`:
@@ -312,7 +525,7 @@ fn normalize_res30(index: u64) -> u64 {
 /// serialize round-trip. The three encoding regimes (non-Hilbert res 0/1,
 /// Hilbert res 2..29, variable-width res 30) all reduce to the same shape
 /// after a small amount of normalization.
-pub fn cell_to_parent(index: u64, parent_resolution: Option<i32>) -> Result<u64, String> {
+pub fn cell_to_parent(index: u64, parent_resolution: Option<i32>) -> Result<u64, A5Error> {
     let parent_resolution = parent_resolution.unwrap_or_else(|| get_resolution(index) - 1);
 
     // Special case: parent of resolution 0 cells is the world cell
@@ -320,16 +533,13 @@ pub fn cell_to_parent(index: u64, parent_resolution: Option<i32>) -> Result<u64,
         return Ok(WORLD_CELL);
     }
     if !(-1..=MAX_RESOLUTION).contains(&parent_resolution) {
-        return Err(format!(
-            "Target resolution ({}) is out of range",
-            parent_resolution
-        ));
+        return Err(A5Error::InvalidResolution(parent_resolution));
     }
     if index == WORLD_CELL {
-        return Err(format!(
-            "Target resolution ({}) must be equal to or less than current resolution (-1)",
-            parent_resolution
-        ));
+        return Err(A5Error::InvalidTargetResolution {
+            target: parent_resolution,
+            current: -1,
+        });
     }
 
     // Normalize res-30 children to the standard res-29 layout. After this,
@@ -379,7 +589,7 @@ static RES0_CELLS: std::sync::LazyLock<Vec<u64>> =
 /// for all higher-resolution subdivisions in the hierarchy.
 ///
 /// Returns Array of 12 cell indices
-pub fn get_res0_cells() -> Result<Vec<u64>, String> {
+pub fn get_res0_cells() -> Result<Vec<u64>, A5Error> {
     Ok(RES0_CELLS.clone())
 }
 
@@ -96,7 +96,7 @@ fn transform_pentagon(pentagon: &mut PentagonShape, matrix: &Mat2) {
 ///
 /// # Returns
 ///
-/// A pentagon shape with transformed vertices
+/// A pentagon shape with transformed vertices, in clockwise order
 pub fn get_pentagon_vertices(
     resolution: i32,
     quintant: usize,
@@ -168,7 +168,7 @@ pub fn get_quintant_vertices(quintant: usize) -> crate::geometry::pentagon::Pent
     pentagon_shape
 }
 
-/// Get face vertices with correct winding order
+/// Get face vertices, in the clockwise order of `PentagonShape`
 ///
 /// # Returns
 ///
@@ -183,10 +183,7 @@ pub fn get_face_vertices() -> PentagonShape {
         vertices.push(Face::new(transformed_x, transformed_y));
     }
 
-    // Need to reverse to obtain correct winding order
-    vertices.reverse();
-
-    // Convert Vec to array for PentagonShape::new
+    // The rotations run counter-clockwise; PentagonShape::new reverses them
     let pentagon_vertices: [Face; 5] = [
         vertices[0],
         vertices[1],
@@ -34,6 +34,7 @@ pub struct Origin {
 
 /// A5 Cell represents a position in the A5 hierarchical indexing system
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct A5Cell {
     /// Origin ID representing one of pentagon face of the dodecahedron
     pub origin_id: OriginId,
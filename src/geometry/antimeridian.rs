@@ -0,0 +1,220 @@
+// A5
+// SPDX-License-Identifier: Apache-2.0
+// Copyright (c) A5 contributors
+
+//! Flat-map output of rings on the sphere.
+//!
+//! Equirectangular consumers (RFC 7946 GeoJSON, tile renderers) need every
+//! longitude within [-180, 180] and draw a ring that jumps across the
+//! antimeridian as a streak around the globe. `split_antimeridian` cuts such
+//! rings into one part per side, and closes rings around a pole along the
+//! pole's own edge of the map.
+
+use crate::coordinate_systems::LonLat;
+use crate::core::coordinate_transforms::normalize_longitude;
+
+/// Latitudes this close to ±90 degrees are taken to be at the pole
+const POLE_TOLERANCE: f64 = 1e-9;
+
+/// Smallest change of longitude between two points, in [-180, 180)
+fn longitude_delta(from: f64, to: f64) -> f64 {
+    normalize_longitude(to - from)
+}
+
+/// Bring a longitude into [-180, 180) by whole turns, leaving it untouched
+/// when already in range
+fn wrap_longitude(longitude: f64) -> f64 {
+    longitude - 360.0 * ((longitude + 180.0) / 360.0).floor()
+}
+
+/// Latitude where the straight segment `a`-`b` reaches `longitude`
+fn latitude_at(a: LonLat, b: LonLat, longitude: f64) -> f64 {
+    let t = (longitude - a.longitude()) / (b.longitude() - a.longitude());
+    a.latitude() + t * (b.latitude() - a.latitude())
+}
+
+/// Replace each vertex at a pole with two vertices along the pole's edge
+/// of the map, at the longitudes of its neighbours, since a single pole
+/// vertex has no meaningful longitude
+fn expand_poles(ring: &[LonLat]) -> Vec<LonLat> {
+    let n = ring.len();
+    let mut expanded = Vec::with_capacity(n + 2);
+    for (i, &vertex) in ring.iter().enumerate() {
+        let latitude = vertex.latitude();
+        if 90.0 - latitude.abs() > POLE_TOLERANCE {
+            expanded.push(vertex);
+            continue;
+        }
+        let pole = 90.0f64.copysign(latitude);
+        expanded.push(LonLat::new(ring[(i + n - 1) % n].longitude(), pole));
+        expanded.push(LonLat::new(ring[(i + 1) % n].longitude(), pole));
+    }
+    expanded
+}
+
+/// Clip a ring to the side of the meridian `longitude` given by `keep_west`
+/// (Sutherland–Hodgman against one line)
+fn clip(ring: &[LonLat], longitude: f64, keep_west: bool) -> Vec<LonLat> {
+    let inside =
+        |p: LonLat| (p.longitude() <= longitude) == keep_west || p.longitude() == longitude;
+    let n = ring.len();
+    let mut clipped = Vec::with_capacity(n + 2);
+    for i in 0..n {
+        let (a, b) = (ring[i], ring[(i + 1) % n]);
+        if inside(a) {
+            clipped.push(a);
+        }
+        if inside(a) != inside(b) {
+            clipped.push(LonLat::new(longitude, latitude_at(a, b, longitude)));
+        }
+    }
+    clipped
+}
+
+/// Twice the signed area in the lon/lat plane, positive for CCW rings.
+/// Taken relative to the first vertex, which keeps tiny rings from
+/// cancelling out.
+fn signed_area(ring: &[LonLat]) -> f64 {
+    let origin = ring[0];
+    let offset = |p: LonLat| {
+        (
+            p.longitude() - origin.longitude(),
+            p.latitude() - origin.latitude(),
+        )
+    };
+    ring.windows(2)
+        .map(|pair| {
+            let ((ax, ay), (bx, by)) = (offset(pair[0]), offset(pair[1]));
+            ax * by - bx * ay
+        })
+        .sum()
+}
+
+/// Ring around a pole: runs once around the globe, so it is closed along
+/// the pole's edge of the map, starting and ending on the antimeridian
+fn close_around_pole(ring: &[LonLat], turn: f64) -> Vec<LonLat> {
+    let n = ring.len();
+    let mean_latitude = ring.iter().map(|p| p.latitude()).sum::<f64>() / n as f64;
+    let pole = 90.0f64.copysign(mean_latitude);
+    let seam = 180.0f64.copysign(turn);
+
+    // Start after the edge that crosses the antimeridian in the direction
+    // of travel
+    let crossing = (0..n)
+        .find(|&i| {
+            let (a, b) = (ring[i].longitude(), ring[(i + 1) % n].longitude());
+            let end = a + longitude_delta(a, b);
+            if turn > 0.0 {
+                end >= 180.0
+            } else {
+                end < -180.0
+            }
+        })
+        .unwrap_or(n - 1);
+    let (a, b) = (ring[crossing], ring[(crossing + 1) % n]);
+    let b_unwrapped = a.longitude() + longitude_delta(a.longitude(), b.longitude());
+    let latitude = latitude_at(a, LonLat::new(b_unwrapped, b.latitude()), seam);
+
+    let mut closed = Vec::with_capacity(n + 4);
+    closed.push(LonLat::new(-seam, latitude));
+    closed.extend((1..=n).map(|k| ring[(crossing + k) % n]));
+    closed.push(LonLat::new(seam, latitude));
+    closed.push(LonLat::new(seam, pole));
+    closed.push(LonLat::new(-seam, pole));
+    closed
+}
+
+/// Split an open ring so that every part lies within [-180, 180] degrees of
+/// longitude.
+///
+/// Edges are taken as straight lines in the lon/lat plane, taking the
+/// shorter way around the globe. A ring crossing the antimeridian is cut into
+/// a part on either side, and a ring enclosing a pole is closed along the
+/// pole's edge of the map. Vertices at a pole are spread along that edge
+/// between the longitudes of their neighbours. Returns open, CCW rings;
+/// a ring needing neither treatment is returned unchanged apart from the
+/// range of its longitudes and its winding.
+pub fn split_antimeridian(ring: &[LonLat]) -> Vec<Vec<LonLat>> {
+    if ring.len() < 3 {
+        return vec![ring.to_vec()];
+    }
+    let ring: Vec<LonLat> = expand_poles(ring)
+        .into_iter()
+        .map(|p| LonLat::new(wrap_longitude(p.longitude()), p.latitude()))
+        .collect();
+    let n = ring.len();
+
+    // Unwrap the longitudes so consecutive vertices are never more than half
+    // the globe apart, by whole turns so that untouched longitudes stay
+    // exact; the net turn is 0, or ±360 around a pole
+    let mut unwrapped: Vec<LonLat> = Vec::with_capacity(n);
+    for &point in &ring {
+        let longitude = match unwrapped.last() {
+            Some(previous) => {
+                let turns = ((previous.longitude() - point.longitude()) / 360.0).round();
+                point.longitude() + 360.0 * turns
+            }
+            None => point.longitude(),
+        };
+        unwrapped.push(LonLat::new(longitude, point.latitude()));
+    }
+    let turn = unwrapped[n - 1].longitude()
+        + longitude_delta(ring[n - 1].longitude(), ring[0].longitude())
+        - ring[0].longitude();
+
+    let mut parts = if turn.abs() > 180.0 {
+        vec![close_around_pole(&ring, turn)]
+    } else {
+        // Shift the ring so its mean longitude lies in [-180, 180), then
+        // cut off whatever reaches past either side
+        let mean = unwrapped.iter().map(|p| p.longitude()).sum::<f64>() / n as f64;
+        let shift = -360.0 * ((mean + 180.0) / 360.0).floor();
+        let shifted: Vec<LonLat> = unwrapped
+            .iter()
+            .map(|p| LonLat::new(p.longitude() + shift, p.latitude()))
+            .collect();
+        let max = shifted
+            .iter()
+            .map(|p| p.longitude())
+            .fold(f64::MIN, f64::max);
+        let min = shifted
+            .iter()
+            .map(|p| p.longitude())
+            .fold(f64::MAX, f64::min);
+
+        let offset = |part: Vec<LonLat>, by: f64| -> Vec<LonLat> {
+            part.into_iter()
+                .map(|p| LonLat::new(p.longitude() + by, p.latitude()))
+                .collect()
+        };
+        if max > 180.0 {
+            vec![
+                clip(&shifted, 180.0, true),
+                offset(clip(&shifted, 180.0, false), -360.0),
+            ]
+        } else if min < -180.0 {
+            vec![
+                clip(&shifted, -180.0, false),
+                offset(clip(&shifted, -180.0, true), 360.0),
+            ]
+        } else {
+            vec![shifted]
+        }
+    };
+
+    // Drop repeated points and the slivers left by a vertex touching the
+    // antimeridian
+    for part in &mut parts {
+        part.dedup();
+        if part.len() > 1 && part.first() == part.last() {
+            part.pop();
+        }
+    }
+    parts.retain(|part| part.len() >= 3 && signed_area(part) != 0.0);
+    for part in &mut parts {
+        if signed_area(part) < 0.0 {
+            part.reverse();
+        }
+    }
+    parts
+}
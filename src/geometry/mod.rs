@@ -2,6 +2,9 @@
 // SPDX-License-Identifier: Apache-2.0
 // Copyright (c) A5 contributors
 
+pub mod antimeridian;
+pub use antimeridian::*;
+
 pub mod pentagon;
 pub use pentagon::*;
 
@@ -26,6 +26,12 @@ fn segments_2d_intersect(p1: Face, p2: Face, p3: Face, p4: Face) -> bool {
     (0.0..=1.0).contains(&t) && (0.0..=1.0).contains(&u)
 }
 
+/// A cell outline in face coordinates.
+///
+/// Vertices are always in clockwise order, i.e. with a non-negative
+/// `get_area`: the constructors reverse them otherwise, and the transforms
+/// preserve it. The dodecahedron projection maps this to clockwise order on
+/// the sphere seen from outside, for every origin.
 #[derive(Debug, Clone)]
 pub struct PentagonShape {
     vertices: Vec<Face>,
@@ -60,6 +66,7 @@ impl PentagonShape {
         pentagon
     }
 
+    /// Twice the signed area, positive for clockwise vertices
     pub fn get_area(&self) -> f64 {
         let mut signed_area = 0.0;
         let n = self.vertices.len();
@@ -6,7 +6,9 @@
 // tests: bounding-cap prefilter, then a trig-free crossing-number test with
 // the winding-number test as a robust fallback.
 
-use crate::coordinate_systems::Cartesian;
+use crate::coordinate_systems::{Cartesian, LonLat};
+use crate::core::constants::AUTHALIC_RADIUS_EARTH;
+use crate::core::coordinate_transforms::{from_lon_lat, to_cartesian, to_lon_lat, to_spherical};
 use crate::geometry::spherical_polygon::{point_in_spherical_polygon, ring_segment_normals};
 
 /// Point-in-polygon for a polygon with holes: inside the outer ring and
@@ -29,12 +31,35 @@ fn point_in_polygon_rings(point: Cartesian, ring_vecs_list: &[Vec<Cartesian>]) -
 /// ±2π there too), so distant probes MUST be rejected by the cap first. The cap
 /// angle is bounded by the farthest ring vertex plus half the longest edge (any
 /// point of an edge arc is within half the edge length of an endpoint).
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct BoundingCap {
     pub center: Cartesian,
     pub min_dot: f64,
 }
 
+impl BoundingCap {
+    /// Whether a unit vector lies within the cap
+    pub fn contains(&self, point: Cartesian) -> bool {
+        point.x() * self.center.x() + point.y() * self.center.y() + point.z() * self.center.z()
+            >= self.min_dot
+    }
+
+    /// Whether a point lies within the cap
+    pub fn contains_lonlat(&self, lonlat: LonLat) -> bool {
+        self.contains(to_cartesian(from_lon_lat(lonlat)))
+    }
+
+    /// Center of the cap as longitude and latitude
+    pub fn center_lonlat(&self) -> LonLat {
+        to_lon_lat(to_spherical(self.center))
+    }
+
+    /// Radius of the cap in meters on the authalic sphere
+    pub fn radius(&self) -> f64 {
+        self.min_dot.clamp(-1.0, 1.0).acos() * AUTHALIC_RADIUS_EARTH
+    }
+}
+
 fn bounding_cap(ring_vecs_list: &[Vec<Cartesian>]) -> BoundingCap {
     let mut cx = 0.0;
     let mut cy = 0.0;
@@ -195,8 +220,7 @@ fn crossing_parity(p: Cartesian, prep: &PreparedPolygon) -> Option<bool> {
 
 /// Full containment test of a point: cap prefilter, then crossing test with winding fallback.
 pub fn point_in_prepared_polygon(p: Cartesian, prep: &PreparedPolygon) -> bool {
-    let cap = &prep.cap;
-    if p.x() * cap.center.x() + p.y() * cap.center.y() + p.z() * cap.center.z() < cap.min_dot {
+    if !prep.cap.contains(p) {
         return false;
     }
     if prep.use_fast {
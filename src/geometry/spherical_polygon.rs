@@ -14,7 +14,10 @@ pub type SphericalPolygon = Vec<Cartesian>;
 ///
 /// Uses the Van Oosterom–Strackee formula.
 /// atan2 keeps full precision for tiny triangles (numerator → area/2) and
-/// does not fold areas above π back into [-π, π].
+/// does not fold areas above π back into [-π, π]. The triple product is
+/// taken over the edges from `v1`, which equals v1 · (v2 × v3) but avoids
+/// the cancellation between vertices that agree in their leading digits,
+/// as for sub-meter cells.
 /// Free-function form avoids the `Vec` heap allocation of
 /// `SphericalTriangleShape::new(vec![v1, v2, v3])?.get_area()` on the
 /// lon_lat_to_cell hot path.
@@ -23,7 +26,8 @@ pub fn spherical_triangle_area(v1: Cartesian, v2: Cartesian, v3: Cartesian) -> R
         + (v1.x() * v2.x() + v1.y() * v2.y() + v1.z() * v2.z())
         + (v2.x() * v3.x() + v2.y() * v3.y() + v2.z() * v3.z())
         + (v3.x() * v1.x() + v3.y() * v1.y() + v3.z() * v1.z());
-    Radians::new_unchecked(2.0 * triple_product(v1, v2, v3).atan2(norm))
+    let numerator = triple_product(v1, subtract(v2, v1), subtract(v3, v1));
+    Radians::new_unchecked(2.0 * numerator.atan2(norm))
 }
 
 /// Spherical point-in-polygon via signed-angle summation. Works for concave
@@ -3,6 +3,7 @@
 // Copyright (c) A5 contributors
 
 use crate::coordinate_systems::{Cartesian, Radians};
+use crate::core::error::A5Error;
 use crate::geometry::{SphericalPolygon, SphericalPolygonShape};
 
 #[derive(Debug)]
@@ -11,9 +12,11 @@ pub struct SphericalTriangleShape {
 }
 
 impl SphericalTriangleShape {
-    pub fn new(vertices: SphericalPolygon) -> Result<Self, String> {
+    pub fn new(vertices: SphericalPolygon) -> Result<Self, A5Error> {
         if vertices.len() != 3 {
-            return Err("SphericalTriangleShape requires exactly 3 vertices".to_string());
+            return Err(A5Error::DegenerateGeometry(
+                "SphericalTriangleShape requires exactly 3 vertices".to_string(),
+            ));
         }
         Ok(Self {
             inner: SphericalPolygonShape::new(vertices),
@@ -0,0 +1,105 @@
+// A5
+// SPDX-License-Identifier: Apache-2.0
+// Copyright (c) A5 contributors
+
+use geo_types::{Coord, LineString, MultiPolygon, Point, Polygon};
+
+use crate::coordinate_systems::LonLat;
+use crate::core::cell::{cell_to_boundary, cell_to_split_boundary, CellToBoundaryOptions};
+use crate::core::error::A5Error;
+use crate::regions::polygon::{multipolygon_to_cells, polygon_to_cells};
+use crate::traversal::line::line_string_to_cells;
+
+/// `x` is longitude and `y` latitude, in degrees
+impl From<LonLat> for Coord<f64> {
+    fn from(lonlat: LonLat) -> Self {
+        Coord {
+            x: lonlat.longitude(),
+            y: lonlat.latitude(),
+        }
+    }
+}
+
+impl From<Coord<f64>> for LonLat {
+    fn from(coord: Coord<f64>) -> Self {
+        LonLat::new(coord.x, coord.y)
+    }
+}
+
+impl From<LonLat> for Point<f64> {
+    fn from(lonlat: LonLat) -> Self {
+        Point(lonlat.into())
+    }
+}
+
+impl From<Point<f64>> for LonLat {
+    fn from(point: Point<f64>) -> Self {
+        point.0.into()
+    }
+}
+
+fn to_lonlats(line: &LineString<f64>) -> Vec<LonLat> {
+    line.0.iter().map(|&coord| coord.into()).collect()
+}
+
+fn to_rings(polygon: &Polygon<f64>) -> Vec<Vec<LonLat>> {
+    std::iter::once(polygon.exterior())
+        .chain(polygon.interiors())
+        .map(to_lonlats)
+        .collect()
+}
+
+/// Convert an A5 cell boundary to a `geo_types::Polygon`, see `cell_to_boundary`.
+///
+/// The exterior ring is closed and wound as in the options, CCW by default;
+/// `closed_ring` is ignored since geo-types always closes polygon rings.
+pub fn cell_to_polygon(
+    cell_id: u64,
+    options: Option<CellToBoundaryOptions>,
+) -> Result<Polygon<f64>, A5Error> {
+    let boundary = cell_to_boundary(cell_id, options)?;
+    let exterior: Vec<Coord<f64>> = boundary.into_iter().map(Coord::from).collect();
+    Ok(Polygon::new(LineString(exterior), Vec::new()))
+}
+
+/// Convert an A5 cell boundary to a `geo_types::MultiPolygon` with every
+/// longitude in [-180, 180], see `cell_to_split_boundary`.
+///
+/// Holds one polygon, or two for a cell crossing the antimeridian.
+pub fn cell_to_multipolygon(
+    cell_id: u64,
+    options: Option<CellToBoundaryOptions>,
+) -> Result<MultiPolygon<f64>, A5Error> {
+    let parts = cell_to_split_boundary(cell_id, options)?;
+    Ok(MultiPolygon(
+        parts
+            .into_iter()
+            .map(|part| {
+                let exterior: Vec<Coord<f64>> = part.into_iter().map(Coord::from).collect();
+                Polygon::new(LineString(exterior), Vec::new())
+            })
+            .collect(),
+    ))
+}
+
+/// `polygon_to_cells` for a `geo_types::Polygon`; interior rings are holes.
+pub fn geo_polygon_to_cells(polygon: &Polygon<f64>, resolution: i32) -> Result<Vec<u64>, A5Error> {
+    polygon_to_cells(&to_rings(polygon), resolution)
+}
+
+/// `multipolygon_to_cells` for a `geo_types::MultiPolygon`.
+pub fn geo_multipolygon_to_cells(
+    multipolygon: &MultiPolygon<f64>,
+    resolution: i32,
+) -> Result<Vec<u64>, A5Error> {
+    let polygons: Vec<Vec<Vec<LonLat>>> = multipolygon.iter().map(to_rings).collect();
+    multipolygon_to_cells(&polygons, resolution)
+}
+
+/// `line_string_to_cells` for a `geo_types::LineString`.
+pub fn geo_line_string_to_cells(
+    line: &LineString<f64>,
+    resolution: i32,
+) -> Result<Vec<u64>, A5Error> {
+    line_string_to_cells(&to_lonlats(line), resolution)
+}
@@ -0,0 +1,207 @@
+// A5
+// SPDX-License-Identifier: Apache-2.0
+// Copyright (c) A5 contributors
+
+use serde_json::{json, Value};
+
+use crate::coordinate_systems::LonLat;
+use crate::core::cell::{cell_to_boundary, cell_to_split_boundary, CellToBoundaryOptions};
+use crate::core::error::A5Error;
+use crate::core::hex::u64_to_hex;
+use crate::core::serialization::get_resolution;
+use crate::io::geometry::{geometry_to_cells, Geometry};
+use crate::regions::dissolve::cells_to_polygons;
+
+/// Options for GeoJSON export
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GeoJsonOptions {
+    /// Number of segments to use for each edge. Pass None to use the resolution of the cell (default: None)
+    pub segments: Option<i32>,
+    /// Pass true to merge a cell set into a single MultiPolygon feature of
+    /// its outlines instead of one feature per cell. Has no effect on a
+    /// single cell (default: false)
+    pub dissolve: bool,
+    /// Pass true to split cells crossing the antimeridian into a MultiPolygon
+    /// and close polar cells along the pole, see `cell_to_split_boundary`.
+    /// Dissolved outlines are not split (default: false)
+    pub split_antimeridian: bool,
+}
+
+fn ring_coordinates(ring: &[LonLat]) -> Vec<[f64; 2]> {
+    ring.iter()
+        .map(|lonlat| [lonlat.longitude(), lonlat.latitude()])
+        .collect()
+}
+
+/// Convert an A5 cell to a GeoJSON Feature with a Polygon geometry.
+///
+/// The ring is closed and CCW, as returned by `cell_to_boundary`. With
+/// `split_antimeridian`, a cell crossing the antimeridian becomes a
+/// MultiPolygon of its parts instead. The properties hold the hex cell id
+/// (`cellIdHex`) and its `resolution`.
+pub fn cell_to_geojson(cell_id: u64, options: Option<GeoJsonOptions>) -> Result<Value, A5Error> {
+    let opts = options.unwrap_or_default();
+    let boundary_options = Some(CellToBoundaryOptions {
+        closed_ring: true,
+        segments: opts.segments,
+        ..Default::default()
+    });
+
+    let geometry = if opts.split_antimeridian {
+        let parts = cell_to_split_boundary(cell_id, boundary_options)?;
+        if parts.len() == 1 {
+            json!({
+                "type": "Polygon",
+                "coordinates": [ring_coordinates(&parts[0])]
+            })
+        } else {
+            let polygons: Vec<Vec<Vec<[f64; 2]>>> = parts
+                .iter()
+                .map(|part| vec![ring_coordinates(part)])
+                .collect();
+            json!({
+                "type": "MultiPolygon",
+                "coordinates": polygons
+            })
+        }
+    } else {
+        let boundary = cell_to_boundary(cell_id, boundary_options)?;
+        json!({
+            "type": "Polygon",
+            "coordinates": [ring_coordinates(&boundary)]
+        })
+    };
+
+    Ok(json!({
+        "type": "Feature",
+        "geometry": geometry,
+        "properties": {
+            "cellIdHex": u64_to_hex(cell_id),
+            "resolution": get_resolution(cell_id)
+        }
+    }))
+}
+
+/// Convert a set of A5 cells to a GeoJSON FeatureCollection.
+///
+/// By default each cell becomes a feature as in `cell_to_geojson`. With
+/// `dissolve` the collection holds a single MultiPolygon feature with one
+/// polygon per edge-connected group of cells, including holes. The cells
+/// may be at mixed resolutions, e.g. the output of `compact`.
+pub fn cells_to_geojson(cells: &[u64], options: Option<GeoJsonOptions>) -> Result<Value, A5Error> {
+    let opts = options.unwrap_or_default();
+
+    let features = if opts.dissolve {
+        let polygons: Vec<Vec<Vec<[f64; 2]>>> = cells_to_polygons(cells, opts.segments)?
+            .iter()
+            .map(|polygon| polygon.iter().map(|ring| ring_coordinates(ring)).collect())
+            .collect();
+        vec![json!({
+            "type": "Feature",
+            "geometry": {
+                "type": "MultiPolygon",
+                "coordinates": polygons
+            },
+            "properties": {}
+        })]
+    } else {
+        cells
+            .iter()
+            .map(|&cell_id| cell_to_geojson(cell_id, Some(opts.clone())))
+            .collect::<Result<Vec<Value>, A5Error>>()?
+    };
+
+    Ok(json!({
+        "type": "FeatureCollection",
+        "features": features
+    }))
+}
+
+fn invalid(message: &str) -> A5Error {
+    A5Error::InvalidGeometry(message.to_string())
+}
+
+fn parse_array<'a>(value: &'a Value, what: &str) -> Result<&'a Vec<Value>, A5Error> {
+    value
+        .as_array()
+        .ok_or_else(|| invalid(&format!("{} must be an array", what)))
+}
+
+/// A GeoJSON position `[longitude, latitude, ...]`; extra ordinates are ignored
+fn parse_position(value: &Value) -> Result<LonLat, A5Error> {
+    let position = parse_array(value, "position")?;
+    match (
+        position.first().and_then(Value::as_f64),
+        position.get(1).and_then(Value::as_f64),
+    ) {
+        (Some(longitude), Some(latitude)) => Ok(LonLat::new(longitude, latitude)),
+        _ => Err(invalid("position must hold at least two numbers")),
+    }
+}
+
+fn parse_positions(value: &Value) -> Result<Vec<LonLat>, A5Error> {
+    parse_array(value, "coordinates")?
+        .iter()
+        .map(parse_position)
+        .collect()
+}
+
+fn parse_rings(value: &Value) -> Result<Vec<Vec<LonLat>>, A5Error> {
+    parse_array(value, "coordinates")?
+        .iter()
+        .map(parse_positions)
+        .collect()
+}
+
+/// Parse a GeoJSON geometry object
+pub fn parse_geojson_geometry(geometry: &Value) -> Result<Geometry, A5Error> {
+    let geometry_type = geometry
+        .get("type")
+        .and_then(Value::as_str)
+        .ok_or_else(|| invalid("geometry has no type"))?;
+
+    if geometry_type == "GeometryCollection" {
+        let geometries = geometry
+            .get("geometries")
+            .ok_or_else(|| invalid("GeometryCollection has no geometries"))?;
+        return Ok(Geometry::GeometryCollection(
+            parse_array(geometries, "geometries")?
+                .iter()
+                .map(parse_geojson_geometry)
+                .collect::<Result<_, _>>()?,
+        ));
+    }
+
+    let coordinates = geometry
+        .get("coordinates")
+        .ok_or_else(|| invalid(&format!("{} has no coordinates", geometry_type)))?;
+
+    Ok(match geometry_type {
+        "Point" => Geometry::Point(parse_position(coordinates)?),
+        "MultiPoint" => Geometry::MultiPoint(parse_positions(coordinates)?),
+        "LineString" => Geometry::LineString(parse_positions(coordinates)?),
+        "MultiLineString" => Geometry::MultiLineString(parse_rings(coordinates)?),
+        "Polygon" => Geometry::Polygon(parse_rings(coordinates)?),
+        "MultiPolygon" => Geometry::MultiPolygon(
+            parse_array(coordinates, "coordinates")?
+                .iter()
+                .map(parse_rings)
+                .collect::<Result<_, _>>()?,
+        ),
+        other => return Err(invalid(&format!("unsupported geometry type {:?}", other))),
+    })
+}
+
+/// Find the cells covering a GeoJSON geometry.
+///
+/// Accepts Point, MultiPoint, LineString, MultiLineString, Polygon,
+/// MultiPolygon and GeometryCollection objects. Points map to the cell
+/// containing them, lines use `line_string_to_cells` and polygons
+/// `polygon_to_cells` (center-point containment). The results of all parts
+/// are merged into one set.
+///
+/// Returns sorted, compacted cell IDs — use `uncompact` to expand to
+/// `resolution`. Malformed input returns `A5Error::InvalidGeometry`.
+pub fn geojson_to_cells(geometry: &Value, resolution: i32) -> Result<Vec<u64>, A5Error> {
+    geometry_to_cells(&parse_geojson_geometry(geometry)?, resolution)
+}
@@ -0,0 +1,131 @@
+// A5
+// SPDX-License-Identifier: Apache-2.0
+// Copyright (c) A5 contributors
+
+use crate::coordinate_systems::LonLat;
+use crate::core::cell::{cell_to_boundary, lonlat_to_cell, CellToBoundaryOptions};
+use crate::core::compact::compact_union;
+use crate::core::error::A5Error;
+use crate::regions::polygon::{multipolygon_to_cells, polygon_to_cells, strip_closing};
+use crate::traversal::line::line_string_to_cells;
+
+/// Deepest nesting of geometry collections accepted by the WKT and WKB
+/// readers, which recurse once per level; deeper input is rejected rather
+/// than risk overflowing the stack
+pub const MAX_NESTING_DEPTH: usize = 64;
+
+/// Simple-features geometry shared by the GeoJSON, WKT and WKB readers and
+/// writers. Polygons are rings `[outer, ...holes]` as in `polygon_to_cells`;
+/// rings may be open or closed.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Geometry {
+    Point(LonLat),
+    MultiPoint(Vec<LonLat>),
+    LineString(Vec<LonLat>),
+    MultiLineString(Vec<Vec<LonLat>>),
+    Polygon(Vec<Vec<LonLat>>),
+    MultiPolygon(Vec<Vec<Vec<LonLat>>>),
+    GeometryCollection(Vec<Geometry>),
+}
+
+/// Reject positions that are NaN or infinite, which would otherwise map to
+/// arbitrary cells
+fn check_finite(positions: &[LonLat]) -> Result<(), A5Error> {
+    match positions
+        .iter()
+        .find(|p| !(p.longitude().is_finite() && p.latitude().is_finite()))
+    {
+        Some(p) => Err(A5Error::InvalidGeometry(format!(
+            "position ({}, {}) is not finite",
+            p.longitude(),
+            p.latitude()
+        ))),
+        None => Ok(()),
+    }
+}
+
+impl Geometry {
+    /// Append the cells covering the geometry to `cells`, without compacting
+    fn collect_cells(&self, resolution: i32, cells: &mut Vec<u64>) -> Result<(), A5Error> {
+        match self {
+            Geometry::Point(point) => {
+                check_finite(std::slice::from_ref(point))?;
+                cells.push(lonlat_to_cell(*point, resolution)?);
+            }
+            Geometry::MultiPoint(points) => {
+                check_finite(points)?;
+                for point in points {
+                    cells.push(lonlat_to_cell(*point, resolution)?);
+                }
+            }
+            Geometry::LineString(line) => {
+                check_finite(line)?;
+                cells.extend(line_string_to_cells(line, resolution)?);
+            }
+            Geometry::MultiLineString(lines) => {
+                for line in lines {
+                    check_finite(line)?;
+                    cells.extend(line_string_to_cells(line, resolution)?);
+                }
+            }
+            Geometry::Polygon(rings) => {
+                rings.iter().try_for_each(|ring| check_finite(ring))?;
+                cells.extend(polygon_to_cells(rings, resolution)?);
+            }
+            Geometry::MultiPolygon(polygons) => {
+                for rings in polygons {
+                    rings.iter().try_for_each(|ring| check_finite(ring))?;
+                }
+                cells.extend(multipolygon_to_cells(polygons, resolution)?);
+            }
+            Geometry::GeometryCollection(geometries) => {
+                for geometry in geometries {
+                    geometry.collect_cells(resolution, cells)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Find the cells covering a geometry.
+///
+/// Points map to the cell containing them, lines use `line_string_to_cells`
+/// and polygons `polygon_to_cells` or `multipolygon_to_cells` (center-point
+/// containment). The results of all parts are merged into one set.
+/// Positions that are NaN or infinite return `A5Error::InvalidGeometry`.
+///
+/// Returns sorted, compacted cell IDs — use `uncompact` to expand to
+/// `resolution`.
+pub fn geometry_to_cells(geometry: &Geometry, resolution: i32) -> Result<Vec<u64>, A5Error> {
+    let mut cells = Vec::new();
+    geometry.collect_cells(resolution, &mut cells)?;
+    compact_union(&cells)
+}
+
+/// Closed boundary of a cell as a polygon, or an empty polygon for the world
+/// cell, which has no boundary
+pub(crate) fn cell_to_polygon(cell_id: u64, segments: Option<i32>) -> Result<Geometry, A5Error> {
+    let boundary = cell_to_boundary(
+        cell_id,
+        Some(CellToBoundaryOptions {
+            closed_ring: true,
+            segments,
+            ..Default::default()
+        }),
+    )?;
+    if boundary.is_empty() {
+        return Ok(Geometry::Polygon(Vec::new()));
+    }
+    Ok(Geometry::Polygon(vec![boundary]))
+}
+
+/// Close a ring for output, repeating the first vertex at the end exactly
+/// once whether or not the input ring was already closed.
+pub fn close_ring(ring: &[LonLat]) -> Vec<LonLat> {
+    let mut closed = strip_closing(ring).to_vec();
+    if let Some(&first) = closed.first() {
+        closed.push(first);
+    }
+    closed
+}
@@ -0,0 +1,11 @@
+// A5
+// SPDX-License-Identifier: Apache-2.0
+// Copyright (c) A5 contributors
+
+#[cfg(feature = "geo")]
+pub mod geo;
+#[cfg(feature = "geojson")]
+pub mod geojson;
+pub mod geometry;
+pub mod wkb;
+pub mod wkt;
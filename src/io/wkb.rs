@@ -0,0 +1,339 @@
+// A5
+// SPDX-License-Identifier: Apache-2.0
+// Copyright (c) A5 contributors
+
+use crate::coordinate_systems::LonLat;
+use crate::core::error::A5Error;
+use crate::io::geometry::{
+    cell_to_polygon, close_ring, geometry_to_cells, Geometry, MAX_NESTING_DEPTH,
+};
+use crate::regions::dissolve::cells_to_polygons;
+
+const POINT: u32 = 1;
+const LINE_STRING: u32 = 2;
+const POLYGON: u32 = 3;
+const MULTI_POINT: u32 = 4;
+const MULTI_LINE_STRING: u32 = 5;
+const MULTI_POLYGON: u32 = 6;
+const GEOMETRY_COLLECTION: u32 = 7;
+
+// EWKB (PostGIS) flags in the high bits of the geometry type
+const EWKB_Z: u32 = 0x8000_0000;
+const EWKB_M: u32 = 0x4000_0000;
+const EWKB_SRID: u32 = 0x2000_0000;
+
+fn write_header(out: &mut Vec<u8>, geometry_type: u32) {
+    out.push(1); // little endian
+    out.extend_from_slice(&geometry_type.to_le_bytes());
+}
+
+fn write_count(out: &mut Vec<u8>, count: usize) {
+    out.extend_from_slice(&(count as u32).to_le_bytes());
+}
+
+fn write_position(out: &mut Vec<u8>, position: &LonLat) {
+    out.extend_from_slice(&position.longitude().to_le_bytes());
+    out.extend_from_slice(&position.latitude().to_le_bytes());
+}
+
+fn write_positions(out: &mut Vec<u8>, positions: &[LonLat]) {
+    write_count(out, positions.len());
+    for position in positions {
+        write_position(out, position);
+    }
+}
+
+fn write_rings(out: &mut Vec<u8>, rings: &[Vec<LonLat>]) {
+    write_count(out, rings.len());
+    for ring in rings {
+        write_positions(out, &close_ring(ring));
+    }
+}
+
+fn write_geometry(out: &mut Vec<u8>, geometry: &Geometry) {
+    match geometry {
+        Geometry::Point(point) => {
+            write_header(out, POINT);
+            write_position(out, point);
+        }
+        Geometry::MultiPoint(points) => {
+            write_header(out, MULTI_POINT);
+            write_count(out, points.len());
+            for point in points {
+                write_geometry(out, &Geometry::Point(*point));
+            }
+        }
+        Geometry::LineString(line) => {
+            write_header(out, LINE_STRING);
+            write_positions(out, line);
+        }
+        Geometry::MultiLineString(lines) => {
+            write_header(out, MULTI_LINE_STRING);
+            write_count(out, lines.len());
+            for line in lines {
+                write_header(out, LINE_STRING);
+                write_positions(out, line);
+            }
+        }
+        Geometry::Polygon(rings) => {
+            write_header(out, POLYGON);
+            write_rings(out, rings);
+        }
+        Geometry::MultiPolygon(polygons) => {
+            write_header(out, MULTI_POLYGON);
+            write_count(out, polygons.len());
+            for rings in polygons {
+                write_header(out, POLYGON);
+                write_rings(out, rings);
+            }
+        }
+        Geometry::GeometryCollection(geometries) => {
+            write_header(out, GEOMETRY_COLLECTION);
+            write_count(out, geometries.len());
+            for geometry in geometries {
+                write_geometry(out, geometry);
+            }
+        }
+    }
+}
+
+/// Encode a geometry as little-endian 2D WKB. Polygon rings are always closed.
+pub fn geometry_to_wkb(geometry: &Geometry) -> Vec<u8> {
+    let mut out = Vec::new();
+    write_geometry(&mut out, geometry);
+    out
+}
+
+/// Convert an A5 cell boundary to a WKB `POLYGON`.
+///
+/// `segments` is the number of points per edge, as in
+/// `CellToBoundaryOptions::segments`. The world cell has no boundary and
+/// gives `POLYGON EMPTY`, a polygon with no rings.
+pub fn cell_to_wkb(cell_id: u64, segments: Option<i32>) -> Result<Vec<u8>, A5Error> {
+    Ok(geometry_to_wkb(&cell_to_polygon(cell_id, segments)?))
+}
+
+/// Convert a set of A5 cells to a WKB `MULTIPOLYGON` of their outlines.
+///
+/// Adjacent cells are merged, see `cells_to_wkt`.
+pub fn cells_to_wkb(cells: &[u64], segments: Option<i32>) -> Result<Vec<u8>, A5Error> {
+    Ok(geometry_to_wkb(&Geometry::MultiPolygon(cells_to_polygons(
+        cells, segments,
+    )?)))
+}
+
+fn invalid(message: &str) -> A5Error {
+    A5Error::InvalidGeometry(format!("WKB: {}", message))
+}
+
+fn finite(position: LonLat) -> Result<LonLat, A5Error> {
+    if position.longitude().is_finite() && position.latitude().is_finite() {
+        Ok(position)
+    } else {
+        Err(invalid("position is not finite"))
+    }
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+    little_endian: bool,
+    /// Geometry collections enclosing the current geometry
+    depth: usize,
+}
+
+impl Reader<'_> {
+    fn take<const N: usize>(&mut self) -> Result<[u8; N], A5Error> {
+        let bytes = self
+            .bytes
+            .get(self.pos..self.pos + N)
+            .ok_or_else(|| invalid("unexpected end of input"))?;
+        self.pos += N;
+        let mut array = [0u8; N];
+        array.copy_from_slice(bytes);
+        Ok(array)
+    }
+
+    fn u32(&mut self) -> Result<u32, A5Error> {
+        let bytes = self.take::<4>()?;
+        Ok(if self.little_endian {
+            u32::from_le_bytes(bytes)
+        } else {
+            u32::from_be_bytes(bytes)
+        })
+    }
+
+    fn f64(&mut self) -> Result<f64, A5Error> {
+        let bytes = self.take::<8>()?;
+        Ok(if self.little_endian {
+            f64::from_le_bytes(bytes)
+        } else {
+            f64::from_be_bytes(bytes)
+        })
+    }
+
+    /// Element count, rejected early if the remaining input cannot hold it
+    fn count(&mut self, min_element_size: usize) -> Result<usize, A5Error> {
+        let count = self.u32()? as usize;
+        if count.saturating_mul(min_element_size) > self.bytes.len() - self.pos {
+            return Err(invalid("element count exceeds input length"));
+        }
+        Ok(count)
+    }
+
+    /// A position of `ordinates` values; those beyond longitude and latitude
+    /// are ignored, and may be NaN or infinite
+    fn any_position(&mut self, ordinates: usize) -> Result<LonLat, A5Error> {
+        let longitude = self.f64()?;
+        let latitude = self.f64()?;
+        for _ in 2..ordinates {
+            self.f64()?;
+        }
+        Ok(LonLat::new(longitude, latitude))
+    }
+
+    /// A position with finite longitude and latitude
+    fn position(&mut self, ordinates: usize) -> Result<LonLat, A5Error> {
+        finite(self.any_position(ordinates)?)
+    }
+
+    fn positions(&mut self, ordinates: usize) -> Result<Vec<LonLat>, A5Error> {
+        let count = self.count(8 * ordinates)?;
+        (0..count).map(|_| self.position(ordinates)).collect()
+    }
+
+    fn rings(&mut self, ordinates: usize) -> Result<Vec<Vec<LonLat>>, A5Error> {
+        let count = self.count(4)?;
+        (0..count).map(|_| self.positions(ordinates)).collect()
+    }
+
+    /// Byte order and type of the next geometry: (base type, ordinates per position)
+    fn header(&mut self) -> Result<(u32, usize), A5Error> {
+        self.little_endian = match self.take::<1>()?[0] {
+            0 => false,
+            1 => true,
+            other => return Err(invalid(&format!("invalid byte order {}", other))),
+        };
+        let geometry_type = self.u32()?;
+
+        // EWKB flags
+        let mut ordinates = 2;
+        if geometry_type & EWKB_Z != 0 {
+            ordinates += 1;
+        }
+        if geometry_type & EWKB_M != 0 {
+            ordinates += 1;
+        }
+        if geometry_type & EWKB_SRID != 0 {
+            self.u32()?;
+        }
+
+        // ISO dimension offsets: 1000 Z, 2000 M, 3000 ZM
+        let iso_type = geometry_type & 0x0fff_ffff;
+        ordinates += match iso_type / 1000 {
+            0 => 0,
+            1 | 2 => 1,
+            3 => 2,
+            _ => return Err(invalid(&format!("invalid geometry type {}", geometry_type))),
+        };
+        Ok((iso_type % 1000, ordinates))
+    }
+
+    /// A member of a multi-geometry, which must be of type `expected`
+    fn member(&mut self, expected: u32) -> Result<usize, A5Error> {
+        let (geometry_type, ordinates) = self.header()?;
+        if geometry_type != expected {
+            return Err(invalid(&format!(
+                "expected member of type {}, found {}",
+                expected, geometry_type
+            )));
+        }
+        Ok(ordinates)
+    }
+
+    fn geometry(&mut self) -> Result<Geometry, A5Error> {
+        let (geometry_type, ordinates) = self.header()?;
+        Ok(match geometry_type {
+            POINT => {
+                let point = self.any_position(ordinates)?;
+                // Empty points are encoded with NaN coordinates
+                if point.longitude().is_nan() && point.latitude().is_nan() {
+                    Geometry::MultiPoint(Vec::new())
+                } else {
+                    Geometry::Point(finite(point)?)
+                }
+            }
+            LINE_STRING => Geometry::LineString(self.positions(ordinates)?),
+            POLYGON => Geometry::Polygon(self.rings(ordinates)?),
+            MULTI_POINT => {
+                let count = self.count(5)?;
+                let mut points = Vec::with_capacity(count);
+                for _ in 0..count {
+                    let ordinates = self.member(POINT)?;
+                    points.push(self.position(ordinates)?);
+                }
+                Geometry::MultiPoint(points)
+            }
+            MULTI_LINE_STRING => {
+                let count = self.count(9)?;
+                let mut lines = Vec::with_capacity(count);
+                for _ in 0..count {
+                    let ordinates = self.member(LINE_STRING)?;
+                    lines.push(self.positions(ordinates)?);
+                }
+                Geometry::MultiLineString(lines)
+            }
+            MULTI_POLYGON => {
+                let count = self.count(9)?;
+                let mut polygons = Vec::with_capacity(count);
+                for _ in 0..count {
+                    let ordinates = self.member(POLYGON)?;
+                    polygons.push(self.rings(ordinates)?);
+                }
+                Geometry::MultiPolygon(polygons)
+            }
+            GEOMETRY_COLLECTION => {
+                if self.depth == MAX_NESTING_DEPTH {
+                    return Err(invalid("geometry collections are nested too deeply"));
+                }
+                let count = self.count(5)?;
+                self.depth += 1;
+                let geometries = (0..count)
+                    .map(|_| self.geometry())
+                    .collect::<Result<_, _>>()?;
+                self.depth -= 1;
+                Geometry::GeometryCollection(geometries)
+            }
+            other => return Err(invalid(&format!("unsupported geometry type {}", other))),
+        })
+    }
+}
+
+/// Parse WKB into a geometry.
+///
+/// Accepts either byte order, ISO and EWKB (PostGIS) type codes, and Z/M
+/// ordinates (ignored). An empty point (NaN coordinates) parses as an empty
+/// `MultiPoint`; any other NaN or infinite position is rejected. Rings may
+/// be open or closed. Geometry collections may be
+/// nested up to `MAX_NESTING_DEPTH` deep.
+pub fn parse_wkb(wkb: &[u8]) -> Result<Geometry, A5Error> {
+    let mut reader = Reader {
+        bytes: wkb,
+        pos: 0,
+        little_endian: true,
+        depth: 0,
+    };
+    let geometry = reader.geometry()?;
+    if reader.pos != wkb.len() {
+        return Err(invalid("trailing bytes after geometry"));
+    }
+    Ok(geometry)
+}
+
+/// Find the cells covering a WKB geometry, see `geometry_to_cells`.
+///
+/// Returns sorted, compacted cell IDs. Malformed input returns
+/// `A5Error::InvalidGeometry`.
+pub fn wkb_to_cells(wkb: &[u8], resolution: i32) -> Result<Vec<u64>, A5Error> {
+    geometry_to_cells(&parse_wkb(wkb)?, resolution)
+}
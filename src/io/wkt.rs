@@ -0,0 +1,333 @@
+// A5
+// SPDX-License-Identifier: Apache-2.0
+// Copyright (c) A5 contributors
+
+use std::fmt::Write;
+
+use crate::coordinate_systems::LonLat;
+use crate::core::error::A5Error;
+use crate::io::geometry::{
+    cell_to_polygon, close_ring, geometry_to_cells, Geometry, MAX_NESTING_DEPTH,
+};
+use crate::regions::dissolve::cells_to_polygons;
+
+fn write_position(out: &mut String, position: &LonLat) {
+    let _ = write!(out, "{} {}", position.longitude(), position.latitude());
+}
+
+/// Write `(item, item, ...)`, or `EMPTY` for no items
+fn write_list<T>(out: &mut String, items: &[T], write_item: impl Fn(&mut String, &T)) {
+    if items.is_empty() {
+        out.push_str("EMPTY");
+        return;
+    }
+    out.push('(');
+    for (i, item) in items.iter().enumerate() {
+        if i > 0 {
+            out.push_str(", ");
+        }
+        write_item(out, item);
+    }
+    out.push(')');
+}
+
+fn write_positions(out: &mut String, positions: &[LonLat]) {
+    write_list(out, positions, write_position);
+}
+
+fn write_rings(out: &mut String, rings: &[Vec<LonLat>]) {
+    write_list(out, rings, |out, ring| {
+        write_positions(out, &close_ring(ring))
+    });
+}
+
+fn write_geometry(out: &mut String, geometry: &Geometry) {
+    match geometry {
+        Geometry::Point(point) => {
+            out.push_str("POINT (");
+            write_position(out, point);
+            out.push(')');
+        }
+        Geometry::MultiPoint(points) => {
+            out.push_str("MULTIPOINT ");
+            write_list(out, points, |out, point| {
+                out.push('(');
+                write_position(out, point);
+                out.push(')');
+            });
+        }
+        Geometry::LineString(line) => {
+            out.push_str("LINESTRING ");
+            write_positions(out, line);
+        }
+        Geometry::MultiLineString(lines) => {
+            out.push_str("MULTILINESTRING ");
+            write_list(out, lines, |out, line| write_positions(out, line));
+        }
+        Geometry::Polygon(rings) => {
+            out.push_str("POLYGON ");
+            write_rings(out, rings);
+        }
+        Geometry::MultiPolygon(polygons) => {
+            out.push_str("MULTIPOLYGON ");
+            write_list(out, polygons, |out, rings| write_rings(out, rings));
+        }
+        Geometry::GeometryCollection(geometries) => {
+            out.push_str("GEOMETRYCOLLECTION ");
+            write_list(out, geometries, write_geometry);
+        }
+    }
+}
+
+/// Format a geometry as WKT. Polygon rings are always closed.
+pub fn geometry_to_wkt(geometry: &Geometry) -> String {
+    let mut out = String::new();
+    write_geometry(&mut out, geometry);
+    out
+}
+
+/// Convert an A5 cell boundary to a WKT `POLYGON`.
+///
+/// `segments` is the number of points per edge, as in
+/// `CellToBoundaryOptions::segments`. The world cell has no boundary and
+/// gives `POLYGON EMPTY`.
+pub fn cell_to_wkt(cell_id: u64, segments: Option<i32>) -> Result<String, A5Error> {
+    Ok(geometry_to_wkt(&cell_to_polygon(cell_id, segments)?))
+}
+
+/// Convert a set of A5 cells to a WKT `MULTIPOLYGON` of their outlines.
+///
+/// Adjacent cells are merged, since polygons of a valid multipolygon may not
+/// share edges. The cells may be at mixed resolutions.
+pub fn cells_to_wkt(cells: &[u64], segments: Option<i32>) -> Result<String, A5Error> {
+    Ok(geometry_to_wkt(&Geometry::MultiPolygon(cells_to_polygons(
+        cells, segments,
+    )?)))
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Word(String),
+    Number(f64),
+    Open,
+    Close,
+    Comma,
+}
+
+fn invalid(message: &str) -> A5Error {
+    A5Error::InvalidGeometry(format!("WKT: {}", message))
+}
+
+fn tokenize(wkt: &str) -> Result<Vec<Token>, A5Error> {
+    let mut tokens = Vec::new();
+    let mut chars = wkt.char_indices().peekable();
+    while let Some(&(start, c)) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+        } else if c == '(' || c == ')' || c == ',' {
+            chars.next();
+            tokens.push(match c {
+                '(' => Token::Open,
+                ')' => Token::Close,
+                _ => Token::Comma,
+            });
+        } else if c.is_ascii_alphabetic() {
+            let mut end = start;
+            while let Some(&(i, c)) = chars.peek() {
+                if !c.is_ascii_alphabetic() {
+                    break;
+                }
+                end = i + c.len_utf8();
+                chars.next();
+            }
+            tokens.push(Token::Word(wkt[start..end].to_ascii_uppercase()));
+        } else if c.is_ascii_digit() || c == '-' || c == '+' || c == '.' {
+            let mut end = start;
+            while let Some(&(i, c)) = chars.peek() {
+                if !(c.is_ascii_alphanumeric() || c == '-' || c == '+' || c == '.') {
+                    break;
+                }
+                end = i + c.len_utf8();
+                chars.next();
+            }
+            let text = &wkt[start..end];
+            // `parse` also accepts signed inf and NaN, and overflows to inf
+            let number = text
+                .parse::<f64>()
+                .ok()
+                .filter(|number| number.is_finite())
+                .ok_or_else(|| invalid(&format!("invalid number {:?}", text)))?;
+            tokens.push(Token::Number(number));
+        } else {
+            return Err(invalid(&format!("unexpected character {:?}", c)));
+        }
+    }
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+    /// Geometry collections enclosing the current geometry
+    depth: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn expect(&mut self, expected: Token) -> Result<(), A5Error> {
+        match self.next() {
+            Some(token) if token == expected => Ok(()),
+            other => Err(invalid(&format!(
+                "expected {:?}, found {:?}",
+                expected, other
+            ))),
+        }
+    }
+
+    fn eat_word(&mut self, word: &str) -> bool {
+        if matches!(self.peek(), Some(Token::Word(w)) if w == word) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// `x y [z [m]]`; ordinates beyond longitude and latitude are ignored
+    fn position(&mut self) -> Result<LonLat, A5Error> {
+        let mut ordinates = Vec::with_capacity(4);
+        while let Some(&Token::Number(value)) = self.peek() {
+            ordinates.push(value);
+            self.pos += 1;
+        }
+        if ordinates.len() < 2 || ordinates.len() > 4 {
+            return Err(invalid("a position needs 2 to 4 ordinates"));
+        }
+        Ok(LonLat::new(ordinates[0], ordinates[1]))
+    }
+
+    /// `(item, item, ...)` or `EMPTY`
+    fn list<T>(
+        &mut self,
+        mut item: impl FnMut(&mut Parser) -> Result<T, A5Error>,
+    ) -> Result<Vec<T>, A5Error> {
+        if self.eat_word("EMPTY") {
+            return Ok(Vec::new());
+        }
+        self.expect(Token::Open)?;
+        let mut items = vec![item(self)?];
+        while self.peek() == Some(&Token::Comma) {
+            self.pos += 1;
+            items.push(item(self)?);
+        }
+        self.expect(Token::Close)?;
+        Ok(items)
+    }
+
+    fn positions(&mut self) -> Result<Vec<LonLat>, A5Error> {
+        self.list(Parser::position)
+    }
+
+    fn rings(&mut self) -> Result<Vec<Vec<LonLat>>, A5Error> {
+        self.list(Parser::positions)
+    }
+
+    /// A MULTIPOINT member, with or without its own parentheses
+    fn multipoint_member(&mut self) -> Result<LonLat, A5Error> {
+        if self.peek() == Some(&Token::Open) {
+            self.pos += 1;
+            let position = self.position()?;
+            self.expect(Token::Close)?;
+            Ok(position)
+        } else {
+            self.position()
+        }
+    }
+
+    fn geometry(&mut self) -> Result<Geometry, A5Error> {
+        let tag = match self.next() {
+            Some(Token::Word(tag)) => tag,
+            other => {
+                return Err(invalid(&format!(
+                    "expected geometry type, found {:?}",
+                    other
+                )))
+            }
+        };
+        // Dimension markers only add ordinates, which `position` skips
+        let _ = self.eat_word("Z") || self.eat_word("M") || self.eat_word("ZM");
+
+        Ok(match tag.as_str() {
+            "POINT" => {
+                let mut points = self.list(Parser::position)?;
+                if points.len() > 1 {
+                    return Err(invalid("POINT has more than one position"));
+                }
+                match points.pop() {
+                    Some(point) => Geometry::Point(point),
+                    None => Geometry::MultiPoint(Vec::new()),
+                }
+            }
+            "MULTIPOINT" => Geometry::MultiPoint(self.list(Parser::multipoint_member)?),
+            "LINESTRING" => Geometry::LineString(self.positions()?),
+            "MULTILINESTRING" => Geometry::MultiLineString(self.rings()?),
+            "POLYGON" => Geometry::Polygon(self.rings()?),
+            "MULTIPOLYGON" => Geometry::MultiPolygon(self.list(Parser::rings)?),
+            "GEOMETRYCOLLECTION" => {
+                if self.depth == MAX_NESTING_DEPTH {
+                    return Err(invalid("geometry collections are nested too deeply"));
+                }
+                self.depth += 1;
+                let geometries = self.list(Parser::geometry)?;
+                self.depth -= 1;
+                Geometry::GeometryCollection(geometries)
+            }
+            other => return Err(invalid(&format!("unsupported geometry type {}", other))),
+        })
+    }
+}
+
+/// Parse WKT into a geometry.
+///
+/// Accepts the seven simple-features types, `EMPTY` members and Z/M
+/// ordinates (ignored). An EWKT `SRID=...;` prefix is skipped. `POINT EMPTY`
+/// parses as an empty `MultiPoint`. Rings may be open or closed. Geometry
+/// collections may be nested up to `MAX_NESTING_DEPTH` deep.
+pub fn parse_wkt(wkt: &str) -> Result<Geometry, A5Error> {
+    let wkt = wkt.trim_start();
+    let wkt = match wkt.get(..5) {
+        Some(prefix) if prefix.eq_ignore_ascii_case("SRID=") => match wkt.find(';') {
+            Some(end) => &wkt[end + 1..],
+            None => return Err(invalid("SRID prefix is not terminated by ';'")),
+        },
+        _ => wkt,
+    };
+
+    let mut parser = Parser {
+        tokens: tokenize(wkt)?,
+        pos: 0,
+        depth: 0,
+    };
+    let geometry = parser.geometry()?;
+    if let Some(token) = parser.peek() {
+        return Err(invalid(&format!("unexpected {:?} after geometry", token)));
+    }
+    Ok(geometry)
+}
+
+/// Find the cells covering a WKT geometry, see `geometry_to_cells`.
+///
+/// Returns sorted, compacted cell IDs. Malformed input returns
+/// `A5Error::InvalidGeometry`.
+pub fn wkt_to_cells(wkt: &str, resolution: i32) -> Result<Vec<u64>, A5Error> {
+    geometry_to_cells(&parse_wkt(wkt)?, resolution)
+}
@@ -17,6 +17,9 @@ pub mod core;
 pub mod geometry;
 #[doc(hidden)]
 #[cfg_attr(not(test), allow(unused))]
+pub mod io;
+#[doc(hidden)]
+#[cfg_attr(not(test), allow(unused))]
 pub mod lattice;
 #[doc(hidden)]
 #[cfg_attr(not(test), allow(unused))]
@@ -33,26 +36,69 @@ pub mod utils;
 
 // PUBLIC API
 // Indexing
-pub use core::cell::{cell_to_boundary, cell_to_lonlat, lonlat_to_cell};
+pub use core::cell::{
+    cell_to_boundary, cell_to_lonlat, cell_to_split_boundary, lonlat_to_cell, BoundaryStart,
+    CellToBoundaryOptions, Winding, MAX_BOUNDARY_SEGMENTS,
+};
 pub use core::hex::{hex_to_u64, u64_to_hex};
+pub use core::ordinal::{cell_to_ordinal, ordinal_to_cell, CellGrid};
 
 // Hierarchy
 pub use core::cell_info::{cell_area, cell_edge_length_avg, get_num_cells, get_num_children};
+pub use core::cell_metrics::{
+    cell_area_exact, cell_edge_lengths, cell_perimeter, cell_shape_metrics, CellShapeMetrics,
+};
 pub use core::serialization::{
-    cell_to_children, cell_to_parent, get_res0_cells, get_resolution, MAX_RESOLUTION, WORLD_CELL,
+    cell_children_iter, cell_to_child_pos, cell_to_children, cell_to_parent, child_pos_to_cell,
+    get_res0_cells, get_resolution, is_valid_cell, validate_cell, CellChildren, MAX_RESOLUTION,
+    WORLD_CELL,
 };
 
 // Compaction
-pub use core::compact::{compact, uncompact};
+pub use core::cell_set::CellSet;
+pub use core::compact::{compact, uncompact, uncompact_iter, UncompactIter};
+pub use core::leaf_range::{cell_to_leaf_range, cells_to_ranges, LeafRange};
+
+// Bounds
+pub use core::cell_bounds::{cell_to_bbox, cell_to_bounding_cap, cells_to_bbox, BoundingBox};
+pub use geometry::prepared_polygon::BoundingCap;
 
 // Traversal
 pub use traversal::cap::spherical_cap;
-pub use traversal::grid_disk::{grid_disk, grid_disk_vertex};
+pub use traversal::edge::{
+    cell_to_edges, cells_to_edge, edge_to_boundary, edge_to_cells, is_valid_edge, EdgeId,
+    MAX_EDGE_RESOLUTION,
+};
+pub use traversal::grid_disk::{
+    grid_disk, grid_disk_distances, grid_disk_distances_vertex, grid_disk_vertex, grid_ring,
+    grid_ring_vertex,
+};
+pub use traversal::grid_path::{grid_distance, grid_path_cells, MAX_GRID_DISTANCE};
 pub use traversal::line::line_string_to_cells;
+pub use traversal::vertex::{
+    cell_to_vertices, is_valid_vertex, vertex_to_cells, vertex_to_lonlat, VertexId,
+};
 
 // Regions
-pub use regions::polygon::polygon_to_cells;
+pub use regions::dissolve::cells_to_polygons;
+pub use regions::polygon::{
+    multipolygon_to_cells, multipolygon_to_cells_with_mode, polygon_to_cells,
+    polygon_to_cells_with_mode, ContainmentMode,
+};
+
+// Export
+#[cfg(feature = "geo")]
+pub use io::geo::{
+    cell_to_multipolygon, cell_to_polygon, geo_line_string_to_cells, geo_multipolygon_to_cells,
+    geo_polygon_to_cells,
+};
+#[cfg(feature = "geojson")]
+pub use io::geojson::{cell_to_geojson, cells_to_geojson, geojson_to_cells, GeoJsonOptions};
+pub use io::wkb::{cell_to_wkb, cells_to_wkb, wkb_to_cells};
+pub use io::wkt::{cell_to_wkt, cells_to_wkt, wkt_to_cells};
 
 // Types
 pub use coordinate_systems::{Degrees, LonLat, Radians};
+pub use core::cell_id::CellId;
+pub use core::error::{A5Error, InvalidCellReason};
 pub use core::utils::A5Cell;
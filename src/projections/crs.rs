@@ -5,6 +5,7 @@
 use crate::coordinate_systems::{Cartesian, Radians, Spherical, SphericalTriangle};
 use crate::core::constants::{DISTANCE_TO_EDGE, DISTANCE_TO_VERTEX};
 use crate::core::coordinate_transforms::to_cartesian;
+use crate::core::error::A5Error;
 use crate::core::origin::get_origins;
 use std::f64::consts::PI;
 
@@ -24,7 +25,7 @@ pub struct CRS {
 }
 
 impl CRS {
-    pub fn new() -> Result<Self, String> {
+    pub fn new() -> Result<Self, A5Error> {
         let mut crs = CRS {
             vertices: Vec::new(),
             invocations: 0,
@@ -35,10 +36,10 @@ impl CRS {
         crs.add_midpoints();
 
         if crs.vertices.len() != 62 {
-            return Err(format!(
+            return Err(A5Error::Projection(format!(
                 "Failed to construct CRS: vertices length is {} instead of 62",
                 crs.vertices.len()
-            ));
+            )));
         }
 
         Ok(crs)
@@ -61,7 +62,7 @@ impl CRS {
         SphericalTriangle::new(self.vertices[0], self.vertices[32], self.vertices[12])
     }
 
-    pub fn get_vertex(&mut self, point: Cartesian) -> Result<Cartesian, String> {
+    pub fn get_vertex(&mut self, point: Cartesian) -> Result<Cartesian, A5Error> {
         self.invocations += 1;
         if self.invocations == 10000 {
             eprintln!("Warning: Too many CRS invocations, results should be cached");
@@ -73,7 +74,9 @@ impl CRS {
             }
         }
 
-        Err("Failed to find vertex in CRS".to_string())
+        Err(A5Error::Projection(
+            "Failed to find vertex in CRS".to_string(),
+        ))
     }
 
     fn add_face_centers(&mut self) {
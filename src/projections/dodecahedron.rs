@@ -7,6 +7,7 @@ use crate::coordinate_systems::{
 };
 use crate::core::constants::{DISTANCE_TO_EDGE, INTERHEDRAL_ANGLE, PI_OVER_5, TWO_PI_OVER_5};
 use crate::core::coordinate_transforms::{to_cartesian, to_face, to_polar, to_spherical};
+use crate::core::error::A5Error;
 use crate::core::origin::get_origins;
 use crate::core::tiling::get_quintant_vertices;
 use crate::core::utils::OriginId;
@@ -34,7 +35,7 @@ pub struct DodecahedronProjection {
 }
 
 impl DodecahedronProjection {
-    pub fn new() -> Result<Self, String> {
+    pub fn new() -> Result<Self, A5Error> {
         let crs = CRS::new()?;
         let equal_area = EqualAreaProjection::new(crs.get_canonical_triangle());
         Ok(DodecahedronProjection {
@@ -52,7 +53,7 @@ impl DodecahedronProjection {
     }
 
     /// Projects spherical coordinates to face coordinates using dodecahedron projection
-    pub fn forward(&mut self, spherical: Spherical, origin_id: OriginId) -> Result<Face, String> {
+    pub fn forward(&mut self, spherical: Spherical, origin_id: OriginId) -> Result<Face, A5Error> {
         self.forward_cartesian(to_cartesian(spherical), origin_id)
     }
 
@@ -63,10 +64,10 @@ impl DodecahedronProjection {
         &mut self,
         unprojected: Cartesian,
         origin_id: OriginId,
-    ) -> Result<Face, String> {
+    ) -> Result<Face, A5Error> {
         let origins = get_origins();
         if (origin_id as usize) >= origins.len() {
-            return Err("Invalid origin ID".to_string());
+            return Err(A5Error::Projection("Invalid origin ID".to_string()));
         }
         let origin = &origins[origin_id as usize];
 
@@ -95,7 +96,7 @@ impl DodecahedronProjection {
     }
 
     /// Unprojects face coordinates to spherical coordinates using dodecahedron projection
-    pub fn inverse(&mut self, face: Face, origin_id: OriginId) -> Result<Spherical, String> {
+    pub fn inverse(&mut self, face: Face, origin_id: OriginId) -> Result<Spherical, A5Error> {
         let polar = to_polar(face);
         let face_triangle_index = self.get_face_triangle_index(polar)?;
 
@@ -118,7 +119,7 @@ impl DodecahedronProjection {
     }
 
     /// Given a polar coordinate, returns the index of the face triangle it belongs to
-    fn get_face_triangle_index(&self, polar: Polar) -> Result<FaceTriangleIndex, String> {
+    fn get_face_triangle_index(&self, polar: Polar) -> Result<FaceTriangleIndex, A5Error> {
         let gamma = polar.gamma().get();
         let index = ((gamma / PI_OVER_5.get()).floor() as i32 + 10) % 10;
         if index < 0 {
@@ -134,9 +135,11 @@ impl DodecahedronProjection {
         face_triangle_index: FaceTriangleIndex,
         reflected: bool,
         squashed: bool,
-    ) -> Result<FaceTriangle, String> {
+    ) -> Result<FaceTriangle, A5Error> {
         if face_triangle_index > 9 {
-            return Err("Face triangle index must be 0-9".to_string());
+            return Err(A5Error::Projection(
+                "Face triangle index must be 0-9".to_string(),
+            ));
         }
         let mut index = face_triangle_index;
         if reflected {
@@ -144,7 +147,9 @@ impl DodecahedronProjection {
         }
 
         if index >= self.face_triangles.len() {
-            return Err("Face triangle index out of bounds".to_string());
+            return Err(A5Error::Projection(
+                "Face triangle index out of bounds".to_string(),
+            ));
         }
 
         if let Some(cached) = &self.face_triangles[index] {
@@ -164,12 +169,14 @@ impl DodecahedronProjection {
     fn get_base_face_triangle(
         &self,
         face_triangle_index: FaceTriangleIndex,
-    ) -> Result<FaceTriangle, String> {
+    ) -> Result<FaceTriangle, A5Error> {
         let quintant = face_triangle_index.div_ceil(2) % 5;
         let vertices = get_quintant_vertices(quintant);
         let verts = vertices.get_vertices();
         if verts.len() < 3 {
-            return Err("Triangle vertices not available".to_string());
+            return Err(A5Error::Projection(
+                "Triangle vertices not available".to_string(),
+            ));
         }
         let (v_center, v_corner1, v_corner2) = (verts[0], verts[1], verts[2]);
 
@@ -193,7 +200,7 @@ impl DodecahedronProjection {
         &self,
         face_triangle_index: FaceTriangleIndex,
         squashed: bool,
-    ) -> Result<FaceTriangle, String> {
+    ) -> Result<FaceTriangle, A5Error> {
         // First obtain ordinary unreflected triangle
         let base = self.get_base_face_triangle(face_triangle_index)?;
         let (mut a, b, c) = (base.a, base.b, base.c);
@@ -221,14 +228,16 @@ impl DodecahedronProjection {
         face_triangle_index: FaceTriangleIndex,
         origin_id: OriginId,
         reflected: bool,
-    ) -> Result<SphericalTriangle, String> {
+    ) -> Result<SphericalTriangle, A5Error> {
         let mut index = 10 * (origin_id as usize) + face_triangle_index; // 0-119
         if reflected {
             index += 120;
         }
 
         if index >= self.spherical_triangles.len() {
-            return Err("Spherical triangle index out of bounds".to_string());
+            return Err(A5Error::Projection(
+                "Spherical triangle index out of bounds".to_string(),
+            ));
         }
 
         if let Some(cached) = &self.spherical_triangles[index] {
@@ -246,10 +255,10 @@ impl DodecahedronProjection {
         face_triangle_index: FaceTriangleIndex,
         origin_id: OriginId,
         reflected: bool,
-    ) -> Result<SphericalTriangle, String> {
+    ) -> Result<SphericalTriangle, A5Error> {
         let origins = get_origins();
         if (origin_id as usize) >= origins.len() {
-            return Err("Invalid origin ID".to_string());
+            return Err(A5Error::Projection("Invalid origin ID".to_string()));
         }
         let origin = &origins[origin_id as usize];
 
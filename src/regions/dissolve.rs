@@ -0,0 +1,366 @@
+// A5
+// SPDX-License-Identifier: Apache-2.0
+// Copyright (c) A5 contributors
+
+use std::collections::{HashMap, HashSet};
+
+use crate::coordinate_systems::{Cartesian, Face, LonLat};
+use crate::core::cell::{get_pentagon, Winding};
+use crate::core::coordinate_transforms::{normalize_longitudes, to_cartesian, to_lon_lat};
+use crate::core::error::A5Error;
+use crate::core::serialization::{
+    cell_to_children, cell_to_parent, deserialize, get_resolution, validate_cell, WORLD_CELL,
+};
+use crate::core::utils::OriginId;
+use crate::geometry::spherical_polygon::ring_winding_sign;
+use crate::projections::dodecahedron::DodecahedronProjection;
+use crate::traversal::edge::same_vertex;
+use crate::traversal::global_neighbors::get_global_cell_neighbors;
+use crate::utils::vector::vec3_distance;
+
+/// A polygon as a list of closed rings: the outer ring first, then holes.
+/// Outer rings are CCW and holes CW, as in RFC 7946.
+pub type DissolvedPolygon = Vec<Vec<LonLat>>;
+
+/// Vertices of a cell in face coordinates and on the unit sphere, in the
+/// order produced by `get_pentagon`.
+struct CellVertices {
+    origin_id: OriginId,
+    face: Vec<Face>,
+    sphere: Vec<Cartesian>,
+}
+
+/// Half-edge `(cell index, edge index)`: edge `i` of a cell runs from vertex
+/// `i` to vertex `i + 1`.
+type HalfEdge = (usize, usize);
+
+/// A vertex is visited at most once per cell meeting there, so walking
+/// around it never takes more steps than this.
+const MAX_VERTEX_VALENCE: usize = 16;
+
+fn find(parents: &mut [usize], mut i: usize) -> usize {
+    while parents[i] != i {
+        parents[i] = parents[parents[i]];
+        i = parents[i];
+    }
+    i
+}
+
+/// Cell of the finest resolution near the outline of a set
+struct OutlineCell {
+    cell: u64,
+    /// Index of the set cell containing it
+    owner: usize,
+    /// Whether the cell touches the outside of the set. The other cells
+    /// only complete the ring of cells around an outline vertex, and their
+    /// edges facing away from it are never traced.
+    on_outline: bool,
+}
+
+/// Cells of a set, indexed to find the one containing a finer cell
+struct SetIndex {
+    owners: HashMap<u64, usize>,
+    resolutions: Vec<i32>,
+}
+
+impl SetIndex {
+    fn new(cells: &[u64]) -> Self {
+        let mut resolutions: Vec<i32> = cells.iter().map(|&c| get_resolution(c)).collect();
+        resolutions.sort_unstable();
+        resolutions.dedup();
+        SetIndex {
+            owners: cells.iter().enumerate().map(|(i, &c)| (c, i)).collect(),
+            resolutions,
+        }
+    }
+
+    /// Index of the set cell containing `cell`, if any
+    fn owner(&self, cell: u64) -> Option<usize> {
+        let resolution = get_resolution(cell);
+        self.resolutions
+            .iter()
+            .take_while(|&&r| r <= resolution)
+            .find_map(|&r| {
+                self.owners
+                    .get(&cell_to_parent(cell, Some(r)).ok()?)
+                    .copied()
+            })
+    }
+}
+
+/// Join edge-sharing cells of the set in `parents`. A cell next to a coarser
+/// one has a neighbor of its own resolution within it, so looking from
+/// each cell at its own resolution finds every pair.
+fn join_neighbors(cells: &[u64], set: &SetIndex, parents: &mut [usize]) {
+    for (index, &cell) in cells.iter().enumerate() {
+        for neighbor in get_global_cell_neighbors(cell, true) {
+            if let Some(neighbor_index) = set.owner(neighbor) {
+                let (root, neighbor_root) = (find(parents, index), find(parents, neighbor_index));
+                parents[root] = neighbor_root;
+            }
+        }
+    }
+}
+
+/// Cells along the outline of a set, at the finest resolution of their
+/// group in `resolutions`, sorted by id.
+///
+/// Children do not nest exactly within their parent, so the outline follows
+/// the cells of the finest resolution. Only cells with a neighbor not wholly
+/// in the set are refined towards it; the others lie inside the outline. The
+/// neighbors in the set of every outline cell are included too, so that all
+/// cells around an outline vertex are present.
+fn outline_cells(
+    cells: &[u64],
+    set: &SetIndex,
+    resolutions: &[i32],
+) -> Result<Vec<OutlineCell>, A5Error> {
+    let mut outline: HashMap<u64, (usize, bool)> = HashMap::new();
+    let mut pending: Vec<(u64, usize)> = cells.iter().copied().zip(0..).collect();
+    while let Some((cell, index)) = pending.pop() {
+        let neighbors = get_global_cell_neighbors(cell, false);
+        let inside: Vec<Option<usize>> = neighbors.iter().map(|&n| set.owner(n)).collect();
+        if inside.iter().all(Option::is_some) {
+            continue;
+        }
+        let resolution = get_resolution(cell);
+        if resolution < resolutions[index] {
+            for child in cell_to_children(cell, Some(resolution + 1))? {
+                pending.push((child, index));
+            }
+            continue;
+        }
+        outline.insert(cell, (index, true));
+        for (neighbor, neighbor_index) in neighbors.into_iter().zip(inside) {
+            if let Some(neighbor_index) = neighbor_index {
+                outline.entry(neighbor).or_insert((neighbor_index, false));
+            }
+        }
+    }
+
+    let mut outline: Vec<OutlineCell> = outline
+        .into_iter()
+        .map(|(cell, (owner, on_outline))| OutlineCell {
+            cell,
+            owner,
+            on_outline,
+        })
+        .collect();
+    outline.sort_unstable_by_key(|outline_cell| outline_cell.cell);
+    Ok(outline)
+}
+
+/// Returns the outline of a set of cells as polygons with holes.
+///
+/// The cells may be at mixed resolutions, e.g. the compacted output of
+/// `polygon_to_cells`, and filling the outlines again with
+/// `multipolygon_to_cells` at the finest of them gives back the same cells.
+/// Polygons do not overlap; each holds its CCW outer ring first, then its CW
+/// holes, all closed.
+///
+/// Edges shared by two cells of the set are removed. Shared edges are found
+/// from the cell adjacency (`get_global_cell_neighbors`) and the vertex
+/// identity of `same_vertex`, rather than from a floating-point union, so
+/// the output has no slivers or gaps. Each edge-connected group of cells
+/// becomes one polygon; holes are the extra boundary rings of the same
+/// group. Cells touching only at a vertex end up in separate polygons.
+///
+/// The outline of each polygon is that of its cells uncompacted to the
+/// finest resolution among them, but only cells near the outline are
+/// refined, so the cost follows the length of the outline rather than the
+/// area of the coarsest cells.
+///
+/// `segments` is the number of points per cell edge, as in
+/// `CellToBoundaryOptions::segments`. Pass None to use the finest resolution
+/// of each polygon.
+pub fn cells_to_polygons(
+    cells: &[u64],
+    segments: Option<i32>,
+) -> Result<Vec<DissolvedPolygon>, A5Error> {
+    if cells.is_empty() || cells.contains(&WORLD_CELL) {
+        // The whole world has no boundary to trace
+        return Ok(Vec::new());
+    }
+
+    let mut set_cells = cells.to_vec();
+    set_cells.sort_unstable();
+    set_cells.dedup();
+    for &cell in &set_cells {
+        validate_cell(cell)?;
+    }
+
+    // Group the cells by edge-connected component, and trace each group at
+    // its finest resolution
+    let set = SetIndex::new(&set_cells);
+    let mut parents: Vec<usize> = (0..set_cells.len()).collect();
+    join_neighbors(&set_cells, &set, &mut parents);
+    let mut group_resolutions = vec![0; set_cells.len()];
+    for (i, &cell) in set_cells.iter().enumerate() {
+        let root = find(&mut parents, i);
+        group_resolutions[root] = group_resolutions[root].max(get_resolution(cell));
+    }
+    let resolutions: Vec<i32> = (0..set_cells.len())
+        .map(|i| group_resolutions[find(&mut parents, i)])
+        .collect();
+    let outline = outline_cells(&set_cells, &set, &resolutions)?;
+    if outline.is_empty() {
+        // The cells cover the whole world
+        return Ok(Vec::new());
+    }
+
+    // Vertices of every outline cell
+    let dodecahedron = DodecahedronProjection::get_thread_local();
+    let mut vertices: Vec<CellVertices> = Vec::with_capacity(outline.len());
+    for outline_cell in &outline {
+        let cell = deserialize(outline_cell.cell)?;
+        let face = get_pentagon(&cell)?.get_vertices_vec().clone();
+        let mut sphere = Vec::with_capacity(face.len());
+        for &vertex in &face {
+            sphere.push(to_cartesian(dodecahedron.inverse(vertex, cell.origin_id)?));
+        }
+        vertices.push(CellVertices {
+            origin_id: cell.origin_id,
+            face,
+            sphere,
+        });
+    }
+    let index: HashMap<u64, usize> = outline
+        .iter()
+        .enumerate()
+        .map(|(i, outline_cell)| (outline_cell.cell, i))
+        .collect();
+
+    // Pair each half-edge with the opposite half-edge of the neighbor across it
+    let mut twins: HashMap<HalfEdge, HalfEdge> = HashMap::new();
+    for (c, outline_cell) in outline.iter().enumerate() {
+        for neighbor_id in get_global_cell_neighbors(outline_cell.cell, true) {
+            let Some(&n) = index.get(&neighbor_id) else {
+                continue;
+            };
+            let a = &vertices[c].sphere;
+            let b = &vertices[n].sphere;
+            for i in 0..a.len() {
+                let (start, end) = (a[i], a[(i + 1) % a.len()]);
+                let length = vec3_distance(&start, &end);
+                if let Some(j) = (0..b.len()).find(|&j| {
+                    same_vertex(&b[j], &end, length)
+                        && same_vertex(&b[(j + 1) % b.len()], &start, length)
+                }) {
+                    twins.insert((c, i), (n, j));
+                }
+            }
+        }
+    }
+
+    // Boundary half-edge following `edge`: rotate around its end vertex
+    // through the cells of the set until reaching an edge without a twin
+    let next_boundary = |(c, i): HalfEdge| -> Result<HalfEdge, A5Error> {
+        let mut candidate = (c, (i + 1) % vertices[c].face.len());
+        for _ in 0..MAX_VERTEX_VALENCE {
+            match twins.get(&candidate) {
+                None => return Ok(candidate),
+                Some(&(n, j)) => candidate = (n, (j + 1) % vertices[n].face.len()),
+            }
+        }
+        Err(A5Error::DegenerateGeometry(
+            "inconsistent cell adjacency while tracing outline".to_string(),
+        ))
+    };
+
+    // Trace boundary rings, grouped by the connected component they bound
+    let mut visited: HashSet<HalfEdge> = HashSet::new();
+    let mut components: HashMap<usize, Vec<Vec<HalfEdge>>> = HashMap::new();
+    let mut component_order: Vec<usize> = Vec::new();
+    for (c, cell) in vertices.iter().enumerate() {
+        if !outline[c].on_outline {
+            continue;
+        }
+        for i in 0..cell.face.len() {
+            let start = (c, i);
+            if twins.contains_key(&start) || visited.contains(&start) {
+                continue;
+            }
+            let mut ring = Vec::new();
+            let mut edge = start;
+            loop {
+                visited.insert(edge);
+                ring.push(edge);
+                edge = next_boundary(edge)?;
+                if edge == start {
+                    break;
+                }
+                if visited.contains(&edge) {
+                    return Err(A5Error::DegenerateGeometry(
+                        "outline ring does not close".to_string(),
+                    ));
+                }
+            }
+            let root = find(&mut parents, outline[c].owner);
+            components
+                .entry(root)
+                .or_insert_with(|| {
+                    component_order.push(root);
+                    Vec::new()
+                })
+                .push(ring);
+        }
+    }
+
+    // The winding of a single cell tells apart outer rings from holes
+    let cell_winding = ring_winding_sign(&vertices[0].sphere);
+
+    let mut polygons = Vec::with_capacity(component_order.len());
+    for root in component_order {
+        let resolution = group_resolutions[root];
+        let segments = segments
+            .unwrap_or_else(|| std::cmp::max(1, 2_i32.pow((6 - resolution).max(0) as u32)))
+            .max(1) as usize;
+        let mut rings = components.remove(&root).unwrap_or_default();
+        let corners = |ring: &[HalfEdge]| -> Vec<Cartesian> {
+            ring.iter().map(|&(c, i)| vertices[c].sphere[i]).collect()
+        };
+        let outer = rings
+            .iter()
+            .position(|ring| ring_winding_sign(&corners(ring)) == cell_winding)
+            .or_else(|| {
+                // Groups covering more than a hemisphere: take the longest ring
+                (0..rings.len()).max_by_key(|&r| rings[r].len())
+            })
+            .unwrap_or(0);
+        rings.swap(0, outer);
+
+        let mut points: Vec<LonLat> = Vec::new();
+        let mut ring_lengths: Vec<usize> = Vec::with_capacity(rings.len());
+        for ring in &rings {
+            let before = points.len();
+            for &(c, i) in ring {
+                let cell = &vertices[c];
+                let a = cell.face[i];
+                let b = cell.face[(i + 1) % cell.face.len()];
+                // Split in face coordinates, matching `cell_to_boundary`
+                for k in 0..segments {
+                    let t = k as f64 / segments as f64;
+                    let point = Face::new(a.x() + (b.x() - a.x()) * t, a.y() + (b.y() - a.y()) * t);
+                    points.push(to_lon_lat(dodecahedron.inverse(point, cell.origin_id)?));
+                }
+            }
+            ring_lengths.push(points.len() - before);
+        }
+
+        // Normalize the whole polygon at once so holes stay next to their outer ring
+        let mut points = normalize_longitudes(points).into_iter();
+        let mut polygon: DissolvedPolygon = Vec::with_capacity(rings.len());
+        for length in ring_lengths {
+            let mut ring: Vec<LonLat> = points.by_ref().take(length).collect();
+            ring.push(ring[0]);
+            // Rings follow the pentagon winding; outer rings run CCW
+            if Winding::PENTAGON != Winding::CounterClockwise {
+                ring.reverse();
+            }
+            polygon.push(ring);
+        }
+        polygons.push(polygon);
+    }
+
+    Ok(polygons)
+}
@@ -2,4 +2,5 @@
 // SPDX-License-Identifier: Apache-2.0
 // Copyright (c) A5 contributors
 
+pub mod dissolve;
 pub mod polygon;
@@ -6,10 +6,12 @@ use std::collections::{HashMap, HashSet};
 
 use crate::coordinate_systems::{Cartesian, LonLat};
 use crate::core::cell::{cell_to_spherical, lonlat_to_cell, spherical_to_cell};
-use crate::core::compact::compact;
-use crate::core::coordinate_transforms::{from_lon_lat, to_cartesian, to_spherical};
+use crate::core::compact::{compact, compact_union};
+use crate::core::constants::AUTHALIC_RADIUS_EARTH;
+use crate::core::coordinate_transforms::{from_lon_lat, to_cartesian, to_lon_lat, to_spherical};
+use crate::core::error::A5Error;
 use crate::core::serialization::{
-    cell_to_children, cell_to_parent, FIRST_HILBERT_RESOLUTION, MAX_RESOLUTION,
+    cell_to_children, cell_to_parent, get_resolution, FIRST_HILBERT_RESOLUTION, MAX_RESOLUTION,
 };
 use crate::geometry::prepared_polygon::{
     point_in_prepared_polygon, prepare_polygon, PreparedPolygon,
@@ -18,7 +20,9 @@ use crate::geometry::spherical_polygon::ring_winding_sign;
 use crate::traversal::cap::estimate_cell_radius;
 use crate::traversal::lattice_flood_fill::{triple_space_flood_fill, FloodInput};
 use crate::traversal::lattice_neighbors::get_lattice_neighbors;
-use crate::utils::great_circle::sample_great_circle_arc;
+use crate::traversal::line::line_string_to_cells;
+use crate::utils::great_circle::{great_circle_distance, sample_great_circle_arc};
+use crate::utils::vector::{cross, length, precompute_slerp, slerp_ctx};
 
 /// Maps each boundary cell to the indices of the ring segments that produced it.
 /// Segment indices are global across rings (outer ring first, then holes).
@@ -37,7 +41,7 @@ fn dense_sample_boundary(
     rings: &[&[LonLat]],
     ring_vecs_list: &[Vec<Cartesian>],
     resolution: i32,
-) -> Result<DenseSampleResult, String> {
+) -> Result<DenseSampleResult, A5Error> {
     let mut boundary_cells: Vec<u64> = Vec::new();
     let mut boundary_set: HashSet<u64> = HashSet::new();
     let mut segment_map: SegmentMap = HashMap::new();
@@ -108,26 +112,27 @@ fn dense_sample_boundary(
     })
 }
 
-/// Filter boundary cells to those whose center is inside the polygon.
+/// Filter boundary cells to those whose center is inside the region.
 ///
 /// For each cell we know which ring segment(s) sampled it. When all of those
 /// segments place the cell on the interior side (cheap signed-dot test), we
 /// accept immediately. When they disagree (vertex / concave corner) or the
-/// cell wasn't recorded, fall back to full PIP.
+/// cell wasn't recorded, fall back to full PIP. With several parts a cell
+/// outside its own part may still be inside another, so rejects are checked too.
 fn filter_boundary_cells(
     boundary_cells: &[u64],
     segment_map: &SegmentMap,
     seg_normals: &[Cartesian],
     seg_signs: &[f64],
-    prep: &PreparedPolygon,
-) -> Result<Vec<u64>, String> {
+    preps: &[PreparedPolygon],
+) -> Result<Vec<u64>, A5Error> {
     let mut out: Vec<u64> = Vec::new();
     for &cell in boundary_cells {
         let cv = to_cartesian(cell_to_spherical(cell)?);
         let segments = match segment_map.get(&cell) {
             Some(s) => s,
             None => {
-                if point_in_prepared_polygon(cv, prep) {
+                if point_in_parts(cv, preps) {
                     out.push(cell);
                 }
                 continue;
@@ -149,8 +154,8 @@ fn filter_boundary_cells(
                 all_inside = false;
             }
         }
-        if ambiguous || (any_inside && !all_inside) {
-            if point_in_prepared_polygon(cv, prep) {
+        if ambiguous || (any_inside && !all_inside) || (!any_inside && preps.len() > 1) {
+            if point_in_parts(cv, preps) {
                 out.push(cell);
             }
         } else if all_inside {
@@ -160,6 +165,11 @@ fn filter_boundary_cells(
     Ok(out)
 }
 
+/// Point-in-polygon against the union of the parts
+fn point_in_parts(p: Cartesian, preps: &[PreparedPolygon]) -> bool {
+    preps.iter().any(|prep| point_in_prepared_polygon(p, prep))
+}
+
 /// Buffer the boundary by one cell using 3-edge lattice neighbors. The shell
 /// matches the connectivity of `triple_space_flood_fill` so the firewall (boundary
 /// + exterior shell) is a tight topological barrier for the subsequent flood.
@@ -188,7 +198,7 @@ fn flood_interior(
     visited: &mut HashSet<u64>,
     boundary_size: usize,
     resolution: i32,
-) -> Result<Vec<u64>, String> {
+) -> Result<Vec<u64>, A5Error> {
     for &cell in interior_seeds {
         visited.insert(cell);
     }
@@ -280,11 +290,7 @@ fn flood_interior(
 
     // Emit fine cells only when not already covered by a coarse parent.
     let mut interior_cells: Vec<u64> = Vec::new();
-    if coarse_interior_set.is_none() {
-        interior_cells.extend_from_slice(interior_seeds);
-        interior_cells.extend(phase1.interior_cells.iter().copied());
-    } else {
-        let coarse_set = coarse_interior_set.as_ref().unwrap();
+    if let Some(coarse_set) = &coarse_interior_set {
         for &cell in interior_seeds {
             let parent = cell_to_parent(cell, Some(parent_res))?;
             if !coarse_set.contains(&parent) {
@@ -298,6 +304,9 @@ fn flood_interior(
             }
         }
         interior_cells.extend(coarse_interior_cells);
+    } else {
+        interior_cells.extend_from_slice(interior_seeds);
+        interior_cells.extend(phase1.interior_cells.iter().copied());
     }
 
     // Phase 3: resume fine BFS, reusing phase 1's packed state.
@@ -315,6 +324,28 @@ fn flood_interior(
     Ok(interior_cells)
 }
 
+/// GeoJSON rings repeat the first vertex at the end — drop the duplicate.
+pub fn strip_closing(ring: &[LonLat]) -> &[LonLat] {
+    if ring.len() > 1 && ring[0] == ring[ring.len() - 1] {
+        &ring[..ring.len() - 1]
+    } else {
+        ring
+    }
+}
+
+/// Which cells `polygon_to_cells_with_mode` returns
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ContainmentMode {
+    /// Cells whose center lies inside the polygon (default)
+    #[default]
+    Center,
+    /// Cells that overlap the polygon at all, e.g. for coverage
+    Intersects,
+    /// Cells lying entirely inside the polygon, e.g. for conservative masks
+    Contains,
+}
+
 /// Find all cells within a polygon using center-point containment: a cell is
 /// included iff its center lies inside the polygon. The result is compacted —
 /// use `uncompact` to expand to the input resolution.
@@ -324,22 +355,67 @@ fn flood_interior(
 /// (GeoJSON-style, first vertex repeated at the end) — closure is automatic
 /// either way. Holes with fewer than 3 distinct vertices are ignored.
 /// Returns sorted, compacted cell IDs whose centers lie inside the polygon.
-pub fn polygon_to_cells(polygon: &[Vec<LonLat>], resolution: i32) -> Result<Vec<u64>, String> {
-    // GeoJSON rings repeat the first vertex at the end — drop the duplicate.
-    fn strip_closing(ring: &[LonLat]) -> &[LonLat] {
-        if ring.len() > 1 && ring[0] == ring[ring.len() - 1] {
-            &ring[..ring.len() - 1]
-        } else {
-            ring
-        }
-    }
+pub fn polygon_to_cells(polygon: &[Vec<LonLat>], resolution: i32) -> Result<Vec<u64>, A5Error> {
+    polygon_to_cells_with_mode(polygon, resolution, ContainmentMode::Center)
+}
 
-    if polygon.is_empty() {
-        return Ok(Vec::new());
-    }
-    let outer = strip_closing(&polygon[0]);
+/// Find the cells of a polygon under the given containment mode.
+///
+/// `Center` matches `polygon_to_cells`. `Intersects` adds every cell whose
+/// pentagon is touched by a ring edge, so cells poking out of the polygon
+/// are kept; `Contains` removes them, so only cells entirely inside remain.
+/// Touched cells are found from the dense boundary samples plus an exact
+/// segment-vs-pentagon walk along each ring, as in `line_string_to_cells`.
+/// Returns sorted, compacted cell IDs.
+pub fn polygon_to_cells_with_mode(
+    polygon: &[Vec<LonLat>],
+    resolution: i32,
+    mode: ContainmentMode,
+) -> Result<Vec<u64>, A5Error> {
+    let parts: Vec<Vec<&[LonLat]>> = part_rings(polygon).into_iter().collect();
+    parts_to_cells(&parts, resolution, mode)
+}
+
+/// Find all cells within a multipolygon using center-point containment,
+/// see `polygon_to_cells`.
+///
+/// `multipolygon` is a list of polygons, each GeoJSON-style rings
+/// `[outer, ...holes]`. Returns one sorted, compacted, deduplicated set.
+pub fn multipolygon_to_cells(
+    multipolygon: &[Vec<Vec<LonLat>>],
+    resolution: i32,
+) -> Result<Vec<u64>, A5Error> {
+    multipolygon_to_cells_with_mode(multipolygon, resolution, ContainmentMode::Center)
+}
+
+/// Find the cells of a multipolygon under the given containment mode, see
+/// `polygon_to_cells_with_mode`.
+///
+/// All parts are filled in one pass rather than one pipeline per part: their
+/// rings are densely sampled into a single boundary, and one flood fill
+/// seeded from every part runs against the shared firewall, so parts that
+/// are adjacent (or overlap) share boundary cells and interior work. The
+/// modes apply to the union of the parts: with `Contains`, a cell straddling
+/// a border between two adjacent parts is kept as long as it lies inside
+/// the union.
+pub fn multipolygon_to_cells_with_mode(
+    multipolygon: &[Vec<Vec<LonLat>>],
+    resolution: i32,
+    mode: ContainmentMode,
+) -> Result<Vec<u64>, A5Error> {
+    let parts: Vec<Vec<&[LonLat]>> = multipolygon
+        .iter()
+        .filter_map(|polygon| part_rings(polygon))
+        .collect();
+    parts_to_cells(&parts, resolution, mode)
+}
+
+/// Rings of one polygon with the closing vertices dropped, or `None` when
+/// the outer ring is degenerate. Holes with fewer than 3 vertices are ignored.
+fn part_rings(polygon: &[Vec<LonLat>]) -> Option<Vec<&[LonLat]>> {
+    let outer = strip_closing(polygon.first()?);
     if outer.len() < 3 {
-        return Ok(Vec::new());
+        return None;
     }
     let mut rings: Vec<&[LonLat]> = vec![outer];
     for hole in &polygon[1..] {
@@ -348,11 +424,88 @@ pub fn polygon_to_cells(polygon: &[Vec<LonLat>], resolution: i32) -> Result<Vec<
             rings.push(hole);
         }
     }
+    Some(rings)
+}
 
+fn parts_to_cells(
+    parts: &[Vec<&[LonLat]>],
+    resolution: i32,
+    mode: ContainmentMode,
+) -> Result<Vec<u64>, A5Error> {
+    if parts.is_empty() {
+        return Ok(Vec::new());
+    }
+    let rings: Vec<&[LonLat]> = parts.iter().flatten().copied().collect();
+    let (ring_vecs_list, preps) = prepare_parts(parts, &rings);
+
+    let (mut cells, boundary_set) = center_cells(&rings, &ring_vecs_list, &preps, resolution)?;
+    match mode {
+        ContainmentMode::Center => compact(&cells),
+        ContainmentMode::Intersects => {
+            // Touched cells may lie under a coarse interior parent
+            cells.extend(touched_cells(&rings, boundary_set, resolution)?);
+            compact_union(&cells)
+        }
+        ContainmentMode::Contains => {
+            // Edges of one part running inside another, such as a border
+            // shared by adjacent parts, do not bound the union
+            let touched = if parts.len() == 1 {
+                touched_cells(&rings, boundary_set, resolution)?
+            } else {
+                outline_cells(&ring_vecs_list, &preps, resolution)?
+            };
+            let mut touched_parents: HashSet<u64> = HashSet::new();
+            for &cell in &touched {
+                touched_parents.insert(cell_to_parent(cell, Some(resolution - 1))?);
+            }
+            let mut contained: Vec<u64> = Vec::with_capacity(cells.len());
+            for cell in cells {
+                if get_resolution(cell) == resolution {
+                    if !touched.contains(&cell) {
+                        contained.push(cell);
+                    }
+                } else if touched_parents.contains(&cell) {
+                    // A coarse interior cell from the flood fill with a touched child
+                    for child in cell_to_children(cell, Some(resolution))? {
+                        if !touched.contains(&child) {
+                            contained.push(child);
+                        }
+                    }
+                } else {
+                    contained.push(cell);
+                }
+            }
+            compact(&contained)
+        }
+    }
+}
+
+/// Cells touched by a ring edge: the dense samples lie on the rings, and the
+/// segment walk of `line_string_to_cells` adds cells clipped between samples.
+fn touched_cells(
+    rings: &[&[LonLat]],
+    boundary_set: HashSet<u64>,
+    resolution: i32,
+) -> Result<HashSet<u64>, A5Error> {
+    let mut touched = boundary_set;
+    for ring in rings {
+        let mut closed = ring.to_vec();
+        closed.push(ring[0]);
+        touched.extend(line_string_to_cells(&closed, resolution)?);
+    }
+    Ok(touched)
+}
+
+/// Authalic-sphere vectors of every ring, and one prepared polygon per part.
+/// `rings` is every ring of `parts`, flattened in order.
+fn prepare_parts(
+    parts: &[Vec<&[LonLat]>],
+    rings: &[&[LonLat]],
+) -> (Vec<Vec<Cartesian>>, Vec<PreparedPolygon>) {
     // Authalic-sphere ring vectors — A5's internal sphere, so cell centers
     // compare directly with no geodetic↔authalic round-trip.
     let mut ring_vecs_list: Vec<Vec<Cartesian>> = Vec::with_capacity(rings.len());
-    for ring in &rings {
+    for ring in rings {
         let mut ring_vecs: Vec<Cartesian> = Vec::with_capacity(ring.len());
         for v in *ring {
             ring_vecs.push(to_cartesian(from_lon_lat(*v)));
@@ -360,24 +513,149 @@ pub fn polygon_to_cells(polygon: &[Vec<LonLat>], resolution: i32) -> Result<Vec<
         ring_vecs_list.push(ring_vecs);
     }
 
-    let prep = prepare_polygon(ring_vecs_list.clone());
+    let mut preps: Vec<PreparedPolygon> = Vec::with_capacity(parts.len());
+    let mut first_ring = 0;
+    for part in parts {
+        let part_vecs = ring_vecs_list[first_ring..first_ring + part.len()].to_vec();
+        preps.push(prepare_polygon(part_vecs));
+        first_ring += part.len();
+    }
+    (ring_vecs_list, preps)
+}
 
+/// Cells touched by the outline of the union of several parts: the stretches
+/// of ring edges with the union on one side only, each traced with the
+/// segment walk of `line_string_to_cells`.
+fn outline_cells(
+    ring_vecs_list: &[Vec<Cartesian>],
+    preps: &[PreparedPolygon],
+    resolution: i32,
+) -> Result<HashSet<u64>, A5Error> {
+    let cell_radius = estimate_cell_radius(resolution);
+    let sample_interval = cell_radius * 0.4;
+    // Far below the cell size, so a probe only leaves the union where the
+    // outline is within a hair of the edge
+    let probe_offset = cell_radius * 1e-3 / AUTHALIC_RADIUS_EARTH;
+
+    let mut touched: HashSet<u64> = HashSet::new();
+    for ring_vecs in ring_vecs_list {
+        let n = ring_vecs.len();
+        for i in 0..n {
+            let (a, b) = (ring_vecs[i], ring_vecs[(i + 1) % n]);
+            let ctx = precompute_slerp(a, b);
+            let point = |t: f64| to_lon_lat(to_spherical(slerp_ctx(a, b, t, Some(ctx))));
+            for (start, end) in outline_stretches(a, b, sample_interval, probe_offset, preps) {
+                touched.extend(line_string_to_cells(
+                    &[point(start), point(end)],
+                    resolution,
+                )?);
+            }
+        }
+    }
+    Ok(touched)
+}
+
+/// Stretches of the arc from `a` to `b` lying on the outline of the union of
+/// `preps`, as ranges of the slerp parameter. The arc is classified once per
+/// `sample_interval` by probing `probe_offset` radians to either side of it,
+/// and each change is then located by bisection.
+fn outline_stretches(
+    a: Cartesian,
+    b: Cartesian,
+    sample_interval: f64,
+    probe_offset: f64,
+    preps: &[PreparedPolygon],
+) -> Vec<(f64, f64)> {
+    let normal = cross(a, b);
+    let norm = length(normal);
+    if norm == 0.0 {
+        return Vec::new();
+    }
+    let ctx = precompute_slerp(a, b);
+    let probe = |p: Cartesian, sign: f64| {
+        let scale = sign * probe_offset / norm;
+        let v = Cartesian::new(
+            p.x() + scale * normal.x(),
+            p.y() + scale * normal.y(),
+            p.z() + scale * normal.z(),
+        );
+        let l = length(v);
+        Cartesian::new(v.x() / l, v.y() / l, v.z() / l)
+    };
+    let inside = |t: f64| {
+        let p = slerp_ctx(a, b, t, Some(ctx));
+        point_in_parts(probe(p, 1.0), preps) && point_in_parts(probe(p, -1.0), preps)
+    };
+    let locate = |mut low: f64, mut high: f64, low_inside: bool| {
+        for _ in 0..48 {
+            let mid = (low + high) / 2.0;
+            if inside(mid) == low_inside {
+                low = mid;
+            } else {
+                high = mid;
+            }
+        }
+        (low + high) / 2.0
+    };
+
+    let steps = ((great_circle_distance(a, b) / sample_interval).ceil() as usize).max(1);
+    let mut stretches: Vec<(f64, f64)> = Vec::new();
+    let mut start: Option<f64> = None;
+    let mut previous: Option<(f64, bool)> = None;
+    for j in 0..steps {
+        let t = (j as f64 + 0.5) / steps as f64;
+        let is_inside = inside(t);
+        let change = match previous {
+            None => Some(0.0),
+            Some((prev_t, prev_inside)) if prev_inside != is_inside => {
+                Some(locate(prev_t, t, prev_inside))
+            }
+            _ => None,
+        };
+        if let Some(at) = change {
+            if is_inside {
+                if let Some(start) = start.take() {
+                    stretches.push((start, at));
+                }
+            } else {
+                start = Some(at);
+            }
+        }
+        previous = Some((t, is_inside));
+    }
+    if let Some(start) = start {
+        stretches.push((start, 1.0));
+    }
+    stretches
+}
+
+/// Center-containment cells of the parts, not yet compacted (the flood fill
+/// may emit parents at `resolution - 1`), together with the dense-sampled
+/// boundary cells. `ring_vecs_list` and `preps` come from `prepare_parts`.
+fn center_cells(
+    rings: &[&[LonLat]],
+    ring_vecs_list: &[Vec<Cartesian>],
+    preps: &[PreparedPolygon],
+    resolution: i32,
+) -> Result<(Vec<u64>, HashSet<u64>), A5Error> {
     let DenseSampleResult {
         boundary_cells,
         boundary_set,
         segment_map,
-    } = dense_sample_boundary(&rings, &ring_vecs_list, resolution)?;
+    } = dense_sample_boundary(rings, ring_vecs_list, resolution)?;
 
     // Flattened per-segment normals and interior-side signs, indexed like the
     // segment map. The polygon interior lies on the *outside* of a hole ring,
     // so hole segments get the opposite sign.
     let mut seg_normals: Vec<Cartesian> = Vec::new();
     let mut seg_signs: Vec<f64> = Vec::new();
-    for (r, ring_vecs) in ring_vecs_list.iter().enumerate() {
-        let sign = (if r == 0 { 1 } else { -1 }) * ring_winding_sign(ring_vecs);
-        for normal in &prep.ring_normals[r] {
-            seg_normals.push(*normal);
-            seg_signs.push(sign as f64);
+    for prep in preps {
+        for (r, ring_vecs) in prep.ring_vecs_list.iter().enumerate() {
+            let sign = (if r == 0 { 1 } else { -1 }) * ring_winding_sign(ring_vecs);
+            for normal in &prep.ring_normals[r] {
+                seg_normals.push(*normal);
+                seg_signs.push(sign as f64);
+            }
         }
     }
 
@@ -386,27 +664,27 @@ pub fn polygon_to_cells(polygon: &[Vec<LonLat>], resolution: i32) -> Result<Vec<
         &segment_map,
         &seg_normals,
         &seg_signs,
-        &prep,
+        preps,
     )?;
 
     // Dense sampling can leave gaps; the shell catches them, classifying each cell.
     let shell_cells = expand_shell(&boundary_cells, &boundary_set);
     if shell_cells.is_empty() {
-        return compact(&filtered_boundary);
+        return Ok((filtered_boundary, boundary_set));
     }
 
     let mut interior_seeds: Vec<u64> = Vec::new();
     let mut visited: HashSet<u64> = boundary_set.clone();
     for cell in shell_cells {
         let cv = to_cartesian(cell_to_spherical(cell)?);
-        if point_in_prepared_polygon(cv, &prep) {
+        if point_in_parts(cv, preps) {
             interior_seeds.push(cell);
         } else {
             visited.insert(cell); // exterior shell (and hole interiors) join the firewall
         }
     }
     if interior_seeds.is_empty() {
-        return compact(&filtered_boundary);
+        return Ok((filtered_boundary, boundary_set));
     }
 
     let interior_cells = flood_interior(
@@ -419,5 +697,5 @@ pub fn polygon_to_cells(polygon: &[Vec<LonLat>], resolution: i32) -> Result<Vec<
     let mut combined: Vec<u64> = Vec::with_capacity(filtered_boundary.len() + interior_cells.len());
     combined.extend(filtered_boundary);
     combined.extend(interior_cells);
-    compact(&combined)
+    Ok((combined, boundary_set))
 }
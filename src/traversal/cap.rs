@@ -5,6 +5,7 @@
 use crate::core::cell::cell_to_spherical;
 use crate::core::cell_info::cell_area;
 use crate::core::constants::AUTHALIC_RADIUS_EARTH;
+use crate::core::error::A5Error;
 use crate::core::origin::haversine;
 use crate::core::serialization::{
     cell_to_children, cell_to_parent, get_resolution, FIRST_HILBERT_RESOLUTION,
@@ -76,7 +77,7 @@ pub fn pick_coarse_resolution(radius: f64, target_res: i32) -> i32 {
 /// Uses hierarchical BFS: starts at a coarse resolution and recursively
 /// subdivides boundary cells, keeping interior cells at coarser resolutions.
 /// Only cells whose centers fall within the radius are included.
-pub fn spherical_cap(cell_id: u64, radius: f64) -> Result<Vec<u64>, String> {
+pub fn spherical_cap(cell_id: u64, radius: f64) -> Result<Vec<u64>, A5Error> {
     let target_res = get_resolution(cell_id);
     let coarse_res = pick_coarse_resolution(radius, target_res);
     let center = cell_to_spherical(cell_id)?;
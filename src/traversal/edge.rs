@@ -0,0 +1,275 @@
+// A5
+// SPDX-License-Identifier: Apache-2.0
+// Copyright (c) A5 contributors
+
+//! Directed edges between edge-sharing cells.
+//!
+//! Edge `i` of a cell runs between vertices `i` and `i + 1` of its pentagon
+//! (or resolution-1 triangle), in the `Winding::PENTAGON` order produced by
+//! `core::tiling::get_pentagon_vertices`. A directed edge is identified by
+//! its origin cell and that index.
+//!
+//! Edge and vertex ids (see `traversal::vertex`) are tagged so that they
+//! never parse as cells or as each other. The top four bits are all set,
+//! which no cell of resolution 0 to 29 has, as its quintant would be 60 or
+//! more. Bit 59 tells vertices from edges, bits 56-58 hold the index, and
+//! the low 56 bits hold the cell id shifted right by 8, which keeps its
+//! resolution marker at bit 1 or above, clear of the three low-bit patterns
+//! used by resolution 30.
+//!
+//! Cell ids of resolutions 29 and 30 use every one of their 64 bits but the
+//! lowest, and resolution 30 alone takes two thirds of all `u64` values, so
+//! there is no room left to tag them. The shift caps the cell resolution at
+//! `MAX_EDGE_RESOLUTION`, whose cells are about 20 cm across.
+
+use std::fmt;
+
+use crate::coordinate_systems::{Cartesian, LonLat};
+use crate::core::cell::{get_pentagon, Winding};
+use crate::core::coordinate_transforms::{
+    normalize_longitudes, to_cartesian, to_lon_lat, to_spherical,
+};
+use crate::core::error::A5Error;
+use crate::core::hex::u64_to_hex;
+use crate::core::serialization::{deserialize, get_resolution, validate_cell};
+use crate::projections::dodecahedron::DodecahedronProjection;
+use crate::traversal::global_neighbors::get_global_cell_neighbors;
+use crate::utils::vector::vec3_distance;
+
+/// Finest resolution whose cells have edge and vertex ids
+pub const MAX_EDGE_RESOLUTION: i32 = 25;
+
+/// Bits set in every edge and vertex id, and in no cell id
+const ID_TAG: u64 = 0b1111 << 60;
+/// Bit set in vertex ids and clear in edge ids
+const VERTEX_BIT: u64 = 1 << 59;
+const INDEX_SHIFT: u32 = 56;
+const INDEX_MASK: u64 = 0b111;
+/// Trailing zero bits of cells up to `MAX_EDGE_RESOLUTION`, shifted out
+const CELL_SHIFT: u32 = 8;
+
+/// Which part of a cell a tagged id refers to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum CellPart {
+    Edge,
+    Vertex,
+}
+
+/// Tagged id of edge or vertex `index` of `cell`, which must be valid and
+/// no finer than `MAX_EDGE_RESOLUTION`
+pub(crate) fn encode_cell_part(cell: u64, index: usize, part: CellPart) -> u64 {
+    let kind = match part {
+        CellPart::Edge => 0,
+        CellPart::Vertex => VERTEX_BIT,
+    };
+    ID_TAG | kind | ((index as u64) << INDEX_SHIFT) | (cell >> CELL_SHIFT)
+}
+
+/// Split a tagged id into its cell and index, or None if it is not a
+/// well-formed id of the given part
+pub(crate) fn decode_cell_part(id: u64, part: CellPart) -> Option<(u64, usize)> {
+    let kind = match part {
+        CellPart::Edge => 0,
+        CellPart::Vertex => VERTEX_BIT,
+    };
+    if id & (ID_TAG | VERTEX_BIT) != ID_TAG | kind {
+        return None;
+    }
+    let index = ((id >> INDEX_SHIFT) & INDEX_MASK) as usize;
+    let cell = (id & ((1 << INDEX_SHIFT) - 1)) << CELL_SHIFT;
+    let resolution = get_resolution(cell);
+    if validate_cell(cell).is_err() || !(0..=MAX_EDGE_RESOLUTION).contains(&resolution) {
+        return None;
+    }
+    // Resolution-1 cells are triangles
+    let count = if resolution == 1 { 3 } else { 5 };
+    (index < count).then_some((cell, index))
+}
+
+/// Identifier of a directed edge between two neighboring cells.
+///
+/// Obtain from `cell_to_edges` or `cells_to_edge`, or from a raw id with
+/// `EdgeId::try_from(u64)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct EdgeId(u64);
+
+impl EdgeId {
+    /// Get the raw 64-bit edge index
+    pub const fn get(&self) -> u64 {
+        self.0
+    }
+
+    /// Origin and destination cells, see `edge_to_cells`
+    pub fn cells(&self) -> Result<(u64, u64), A5Error> {
+        edge_to_cells(*self)
+    }
+
+    /// End points, see `edge_to_boundary`
+    pub fn boundary(&self) -> Result<[LonLat; 2], A5Error> {
+        edge_to_boundary(*self)
+    }
+
+    fn origin_and_index(&self) -> (u64, usize) {
+        decode_cell_part(self.0, CellPart::Edge).expect("edge ids are validated on creation")
+    }
+}
+
+impl TryFrom<u64> for EdgeId {
+    type Error = A5Error;
+
+    fn try_from(index: u64) -> Result<Self, Self::Error> {
+        if is_valid_edge(index) {
+            Ok(EdgeId(index))
+        } else {
+            Err(A5Error::InvalidEdge(index))
+        }
+    }
+}
+
+impl From<EdgeId> for u64 {
+    fn from(edge: EdgeId) -> Self {
+        edge.0
+    }
+}
+
+impl fmt::Display for EdgeId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&u64_to_hex(self.0))
+    }
+}
+
+/// Unprojected vertices of a cell, in pentagon order
+pub fn cell_vertices(cell: u64) -> Result<Vec<Cartesian>, A5Error> {
+    let cell_data = deserialize(cell)?;
+    let pentagon = get_pentagon(&cell_data)?;
+    let dodecahedron = DodecahedronProjection::get_thread_local();
+    pentagon
+        .get_vertices_vec()
+        .iter()
+        .map(|&vertex| {
+            Ok(to_cartesian(
+                dodecahedron.inverse(vertex, cell_data.origin_id)?,
+            ))
+        })
+        .collect()
+}
+
+/// Largest distance between coinciding vertices of neighboring cells, as a
+/// fraction of the edge length
+pub(crate) const VERTEX_TOLERANCE: f64 = 1e-6;
+
+/// Whether `a` and `b` are the same vertex of neighboring cells, given the
+/// length of a cell edge meeting there
+pub(crate) fn same_vertex(a: &Cartesian, b: &Cartesian, edge_length: f64) -> bool {
+    vec3_distance(a, b) < VERTEX_TOLERANCE * edge_length
+}
+
+/// How far the segment `a`-`b` is from being an edge of a cell with the
+/// given vertices: the larger distance of its ends from the nearest vertex
+fn edge_mismatch(a: Cartesian, b: Cartesian, vertices: &[Cartesian]) -> f64 {
+    let nearest = |p: Cartesian| {
+        vertices
+            .iter()
+            .map(|v| vec3_distance(&p, v))
+            .fold(f64::INFINITY, f64::min)
+    };
+    nearest(a).max(nearest(b))
+}
+
+/// Ends of edge `index`, from vertex `index` to the next
+fn edge_ends(vertices: &[Cartesian], index: usize) -> (Cartesian, Cartesian) {
+    (vertices[index], vertices[(index + 1) % vertices.len()])
+}
+
+/// Validate a cell and check it is fine enough to hold an index below its marker
+pub fn check_edge_resolution(cell: u64) -> Result<(), A5Error> {
+    validate_cell(cell)?;
+    let resolution = get_resolution(cell);
+    if !(0..=MAX_EDGE_RESOLUTION).contains(&resolution) {
+        return Err(A5Error::InvalidResolution(resolution));
+    }
+    Ok(())
+}
+
+/// Whether `edge` is a well-formed directed edge id. Edge ids are never
+/// valid cell or vertex ids.
+pub fn is_valid_edge(edge: u64) -> bool {
+    decode_cell_part(edge, CellPart::Edge).is_some()
+}
+
+/// Returns the directed edges leading out of a cell, in edge index order.
+///
+/// Cells have 5 edges, except resolution-1 cells which have 3. Returns
+/// `A5Error::InvalidResolution` for the world cell and for resolutions
+/// above `MAX_EDGE_RESOLUTION`.
+pub fn cell_to_edges(cell: u64) -> Result<Vec<EdgeId>, A5Error> {
+    check_edge_resolution(cell)?;
+    let edge_count = if get_resolution(cell) == 1 { 3 } else { 5 };
+    Ok((0..edge_count)
+        .map(|index| EdgeId(encode_cell_part(cell, index, CellPart::Edge)))
+        .collect())
+}
+
+/// Returns the directed edge from `origin` to the neighboring `destination`.
+///
+/// Returns `A5Error::NotNeighbors` if the cells do not share an edge,
+/// including when they are at different resolutions.
+pub fn cells_to_edge(origin: u64, destination: u64) -> Result<EdgeId, A5Error> {
+    check_edge_resolution(origin)?;
+    validate_cell(destination)?;
+    if !get_global_cell_neighbors(origin, true).contains(&destination) {
+        return Err(A5Error::NotNeighbors {
+            origin,
+            destination,
+        });
+    }
+
+    let vertices = cell_vertices(origin)?;
+    let destination_vertices = cell_vertices(destination)?;
+    let (_, index) = (0..vertices.len())
+        .map(|i| {
+            let (a, b) = edge_ends(&vertices, i);
+            (edge_mismatch(a, b, &destination_vertices), i)
+        })
+        .min_by(|x, y| x.0.total_cmp(&y.0))
+        .expect("cells have at least 3 edges");
+    Ok(EdgeId(encode_cell_part(origin, index, CellPart::Edge)))
+}
+
+/// Returns the `(origin, destination)` cells of a directed edge.
+pub fn edge_to_cells(edge: EdgeId) -> Result<(u64, u64), A5Error> {
+    let (origin, index) = edge.origin_and_index();
+    let (a, b) = edge_ends(&cell_vertices(origin)?, index);
+
+    // The neighbor across the edge is the one with both ends as vertices
+    let mut destination = None;
+    let mut best = f64::INFINITY;
+    for neighbor in get_global_cell_neighbors(origin, true) {
+        let mismatch = edge_mismatch(a, b, &cell_vertices(neighbor)?);
+        if mismatch < best {
+            best = mismatch;
+            destination = Some(neighbor);
+        }
+    }
+    let destination = destination.ok_or(A5Error::InvalidEdge(edge.get()))?;
+    Ok((origin, destination))
+}
+
+/// Returns the two end points of a directed edge, ordered so that the
+/// origin cell lies to the left when walking from the first to the second.
+pub fn edge_to_boundary(edge: EdgeId) -> Result<[LonLat; 2], A5Error> {
+    let (origin, index) = edge.origin_and_index();
+    let (a, b) = edge_ends(&cell_vertices(origin)?, index);
+
+    // Keep the cell to the left, as in a counter-clockwise ring
+    let (a, b) = if Winding::PENTAGON == Winding::CounterClockwise {
+        (a, b)
+    } else {
+        (b, a)
+    };
+    let ends = normalize_longitudes(vec![
+        to_lon_lat(to_spherical(a)),
+        to_lon_lat(to_spherical(b)),
+    ]);
+    Ok([ends[0], ends[1]])
+}
@@ -28,8 +28,7 @@ fn serialize_res1(origin: &Origin, quintant: usize) -> u64 {
 fn get_res0_neighbors(origin: &Origin) -> Vec<u64> {
     let origins = get_origins();
     let mut neighbor_set = BTreeSet::new();
-    for q in 0..5 {
-        let (adjacent_face_id, _) = FACE_ADJACENCY[origin.id as usize][q];
+    for &(adjacent_face_id, _) in &FACE_ADJACENCY[origin.id as usize] {
         let adjacent_origin = &origins[adjacent_face_id as usize];
         if let Ok(cell_id) = serialize(&A5Cell {
             origin_id: adjacent_origin.id,
@@ -5,13 +5,34 @@
 use std::collections::HashSet;
 
 use crate::core::compact::compact;
+use crate::core::error::A5Error;
+use crate::core::serialization::{validate_cell, WORLD_CELL};
 use crate::traversal::global_neighbors::get_global_cell_neighbors;
 
+/// Cells adjacent to `frontier` that are in neither it nor `prev_frontier`.
+///
+/// With the two previous rings of a BFS as input, this is the next ring.
+fn next_ring(
+    prev_frontier: &HashSet<u64>,
+    frontier: &HashSet<u64>,
+    edge_only: bool,
+) -> HashSet<u64> {
+    let mut next_frontier: HashSet<u64> = HashSet::new();
+    for &cid in frontier {
+        for neighbor in get_global_cell_neighbors(cid, edge_only) {
+            if !prev_frontier.contains(&neighbor) && !frontier.contains(&neighbor) {
+                next_frontier.insert(neighbor);
+            }
+        }
+    }
+    next_frontier
+}
+
 /// BFS grid disk with progressive compaction.
 ///
 /// Uses a sliding-window dedup approach: only the previous and current frontier
 /// rings are kept in memory for deduplication.
-fn grid_disk_bfs(cell_id: u64, k: usize, edge_only: bool) -> Result<Vec<u64>, String> {
+fn grid_disk_bfs(cell_id: u64, k: usize, edge_only: bool) -> Result<Vec<u64>, A5Error> {
     if k == 0 {
         return Ok(vec![cell_id]);
     }
@@ -22,17 +43,7 @@ fn grid_disk_bfs(cell_id: u64, k: usize, edge_only: bool) -> Result<Vec<u64>, St
     frontier.insert(cell_id);
 
     for _ring in 1..=k {
-        let mut next_frontier: HashSet<u64> = HashSet::new();
-        for &cid in &frontier {
-            for neighbor in get_global_cell_neighbors(cid, edge_only) {
-                if !prev_frontier.contains(&neighbor)
-                    && !frontier.contains(&neighbor)
-                    && !next_frontier.contains(&neighbor)
-                {
-                    next_frontier.insert(neighbor);
-                }
-            }
-        }
+        let next_frontier = next_ring(&prev_frontier, &frontier, edge_only);
 
         // Evict prevFrontier -- these cells are >=2 rings behind the new frontier
         for &cid in &prev_frontier {
@@ -61,12 +72,88 @@ fn grid_disk_bfs(cell_id: u64, k: usize, edge_only: bool) -> Result<Vec<u64>, St
 
 /// Compute the grid disk of edge-sharing neighbors within k hops.
 /// Returns a sorted, compacted list of cell IDs including the center cell.
-pub fn grid_disk(cell_id: u64, k: usize) -> Result<Vec<u64>, String> {
+pub fn grid_disk(cell_id: u64, k: usize) -> Result<Vec<u64>, A5Error> {
     grid_disk_bfs(cell_id, k, true)
 }
 
 /// Compute the grid disk of all neighbors (edge + vertex sharing) within k hops.
 /// Returns a sorted, compacted list of cell IDs including the center cell.
-pub fn grid_disk_vertex(cell_id: u64, k: usize) -> Result<Vec<u64>, String> {
+pub fn grid_disk_vertex(cell_id: u64, k: usize) -> Result<Vec<u64>, A5Error> {
     grid_disk_bfs(cell_id, k, false)
 }
+
+/// Visit BFS rings 1 to k in order, keeping only two rings for deduplication.
+///
+/// The world cell has no neighbors, so all of its rings are empty.
+fn for_each_ring(
+    cell_id: u64,
+    k: usize,
+    edge_only: bool,
+    mut visit: impl FnMut(&HashSet<u64>),
+) -> Result<(), A5Error> {
+    validate_cell(cell_id)?;
+    let mut prev_frontier: HashSet<u64> = HashSet::new();
+    let mut frontier: HashSet<u64> = if cell_id == WORLD_CELL {
+        HashSet::new()
+    } else {
+        HashSet::from([cell_id])
+    };
+    for _ring in 1..=k {
+        let next_frontier = next_ring(&prev_frontier, &frontier, edge_only);
+        visit(&next_frontier);
+        prev_frontier = frontier;
+        frontier = next_frontier;
+    }
+    Ok(())
+}
+
+fn grid_ring_bfs(cell_id: u64, k: usize, edge_only: bool) -> Result<Vec<u64>, A5Error> {
+    let mut ring = vec![cell_id];
+    for_each_ring(cell_id, k, edge_only, |cells| {
+        ring.clear();
+        ring.extend(cells);
+    })?;
+    ring.sort_unstable();
+    Ok(ring)
+}
+
+fn grid_disk_distances_bfs(
+    cell_id: u64,
+    k: usize,
+    edge_only: bool,
+) -> Result<Vec<Vec<u64>>, A5Error> {
+    let mut rings = vec![vec![cell_id]];
+    for_each_ring(cell_id, k, edge_only, |cells| {
+        let mut ring: Vec<u64> = cells.iter().copied().collect();
+        ring.sort_unstable();
+        rings.push(ring);
+    })?;
+    Ok(rings)
+}
+
+/// Compute the hollow ring of cells exactly k edge-sharing hops away.
+/// Returns a sorted list of uncompacted cell IDs; k = 0 gives the cell itself.
+pub fn grid_ring(cell_id: u64, k: usize) -> Result<Vec<u64>, A5Error> {
+    grid_ring_bfs(cell_id, k, true)
+}
+
+/// Compute the hollow ring of cells exactly k hops away, counting both edge
+/// and vertex sharing neighbors as one hop.
+/// Returns a sorted list of uncompacted cell IDs; k = 0 gives the cell itself.
+pub fn grid_ring_vertex(cell_id: u64, k: usize) -> Result<Vec<u64>, A5Error> {
+    grid_ring_bfs(cell_id, k, false)
+}
+
+/// Compute the grid disk of edge-sharing neighbors within k hops, grouped by
+/// distance: entry i holds the sorted, uncompacted cells exactly i hops away.
+/// Together the k + 1 rings hold the same cells as `grid_disk`.
+pub fn grid_disk_distances(cell_id: u64, k: usize) -> Result<Vec<Vec<u64>>, A5Error> {
+    grid_disk_distances_bfs(cell_id, k, true)
+}
+
+/// Compute the grid disk of all neighbors (edge + vertex sharing) within k
+/// hops, grouped by distance as in `grid_disk_distances`.
+/// Together the k + 1 rings hold the same cells as `grid_disk_vertex`.
+pub fn grid_disk_distances_vertex(cell_id: u64, k: usize) -> Result<Vec<Vec<u64>>, A5Error> {
+    grid_disk_distances_bfs(cell_id, k, false)
+}
@@ -0,0 +1,146 @@
+// A5
+// SPDX-License-Identifier: Apache-2.0
+// Copyright (c) A5 contributors
+
+use std::cmp::Reverse;
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, HashMap};
+
+use crate::coordinate_systems::Cartesian;
+use crate::core::cell::cell_to_lonlat;
+use crate::core::cell_info::cell_edge_length_avg;
+use crate::core::coordinate_transforms::{from_lon_lat, to_cartesian};
+use crate::core::error::A5Error;
+use crate::core::serialization::{get_resolution, validate_cell, FIRST_HILBERT_RESOLUTION};
+use crate::traversal::global_neighbors::get_global_cell_neighbors;
+use crate::utils::great_circle::great_circle_distance;
+
+/// Longest distance between the centers of two edge-sharing cells, in
+/// average edge lengths, for resolutions 2 to 10. Measured over every cell
+/// and rounded up; it keeps growing slightly with the resolution, so the A*
+/// heuristic stays admissible without a looser margin.
+const MAX_EDGE_HOPS: [f64; 9] = [
+    1.532, 1.548, 1.566, 1.578, 1.584, 1.586, 1.588, 1.588, 1.589,
+];
+
+/// Limit of `MAX_EDGE_HOPS` at finer resolutions, where the measured
+/// maximum converges to about 1.5885
+const MAX_EDGE_HOP_LIMIT: f64 = 1.589;
+
+/// Largest grid distance searched for. Cells that are certainly further
+/// apart fail with `A5Error::PathSearchLimit` before any search, since the
+/// cells a search visits grow with the square of the distance.
+pub const MAX_GRID_DISTANCE: u64 = 1024;
+
+/// Most cells a search may visit, well above what paths within
+/// `MAX_GRID_DISTANCE` need
+const MAX_SEARCH_CELLS: usize = 1 << 21;
+
+/// Longest center-to-center hop between edge-sharing cells at `resolution`
+fn max_edge_hop(resolution: i32) -> f64 {
+    let ratio = MAX_EDGE_HOPS
+        .get((resolution - FIRST_HILBERT_RESOLUTION) as usize)
+        .copied()
+        .unwrap_or(MAX_EDGE_HOP_LIMIT);
+    ratio * cell_edge_length_avg(resolution)
+}
+
+fn center(cell: u64) -> Result<Cartesian, A5Error> {
+    Ok(to_cartesian(from_lon_lat(cell_to_lonlat(cell)?)))
+}
+
+/// Shortest chain of edge-sharing cells from `start` to `end`, both included.
+///
+/// A* over `get_global_cell_neighbors`, guided by the great-circle distance
+/// to `end` divided by the longest hop at the resolution. That bound never
+/// overestimates the hops left, so the first path found is a shortest one,
+/// while the search stays in an ellipse around the great circle instead of
+/// growing a full disk.
+fn shortest_path(start: u64, end: u64) -> Result<Vec<u64>, A5Error> {
+    validate_cell(start)?;
+    validate_cell(end)?;
+    let resolution = get_resolution(start);
+    if get_resolution(end) != resolution {
+        return Err(A5Error::ResolutionMismatch {
+            start: resolution,
+            end: get_resolution(end),
+        });
+    }
+    if start == end {
+        return Ok(vec![start]);
+    }
+
+    // Resolutions 0 and 1 have at most 60 cells: plain BFS
+    let goal = center(end)?;
+    let max_hop = max_edge_hop(resolution);
+    let heuristic = |cell: u64| -> Result<u64, A5Error> {
+        if resolution < FIRST_HILBERT_RESOLUTION {
+            return Ok(0);
+        }
+        Ok((great_circle_distance(center(cell)?, goal) / max_hop) as u64)
+    };
+
+    // Hops from `start` and the previous cell on the best path found so far
+    let mut visited: HashMap<u64, (u64, u64)> = HashMap::new();
+    visited.insert(start, (0, start));
+    // Ordered by estimated total hops, then by estimated hops left
+    let mut queue = BinaryHeap::new();
+    let h = heuristic(start)?;
+    if h > MAX_GRID_DISTANCE {
+        return Err(A5Error::PathSearchLimit { start, end });
+    }
+    queue.push(Reverse((h, h, 0u64, start)));
+
+    while let Some(Reverse((_, _, hops, cell))) = queue.pop() {
+        if cell == end {
+            let mut path = vec![end];
+            let mut cell = end;
+            while cell != start {
+                cell = visited[&cell].1;
+                path.push(cell);
+            }
+            path.reverse();
+            return Ok(path);
+        }
+        if hops > visited[&cell].0 {
+            continue; // stale entry, a shorter route was queued later
+        }
+        for neighbor in get_global_cell_neighbors(cell, true) {
+            match visited.entry(neighbor) {
+                Entry::Occupied(entry) if entry.get().0 <= hops + 1 => continue,
+                Entry::Occupied(mut entry) => {
+                    entry.insert((hops + 1, cell));
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert((hops + 1, cell));
+                }
+            }
+            if visited.len() > MAX_SEARCH_CELLS {
+                return Err(A5Error::PathSearchLimit { start, end });
+            }
+            let h = heuristic(neighbor)?;
+            queue.push(Reverse((hops + 1 + h, h, hops + 1, neighbor)));
+        }
+    }
+
+    Err(A5Error::NoPath { start, end })
+}
+
+/// Number of edge-sharing hops between two cells of the same resolution.
+///
+/// Works across quintant and dodecahedron-face boundaries. Returns an error
+/// if either cell is invalid, `A5Error::ResolutionMismatch` if the
+/// resolutions differ, and may return `A5Error::PathSearchLimit` for cells
+/// more than `MAX_GRID_DISTANCE` apart.
+pub fn grid_distance(start: u64, end: u64) -> Result<u64, A5Error> {
+    Ok(shortest_path(start, end)?.len() as u64 - 1)
+}
+
+/// A shortest chain of edge-sharing cells from `start` to `end`.
+///
+/// The result begins with `start` and ends with `end`, each cell shares an
+/// edge with the next, and it has `grid_distance(start, end) + 1` cells.
+/// When several shortest chains exist, which one is returned is unspecified.
+pub fn grid_path_cells(start: u64, end: u64) -> Result<Vec<u64>, A5Error> {
+    shortest_path(start, end)
+}
@@ -7,6 +7,7 @@ use std::collections::HashSet;
 use crate::coordinate_systems::LonLat;
 use crate::core::cell::{cell_intersects_segment, lonlat_to_cell};
 use crate::core::coordinate_transforms::{from_lon_lat, to_cartesian, to_lon_lat, to_spherical};
+use crate::core::error::A5Error;
 use crate::traversal::cap::estimate_cell_radius;
 use crate::traversal::lattice_neighbors::get_lattice_neighbors;
 use crate::utils::great_circle::sample_great_circle_arc;
@@ -22,7 +23,7 @@ use crate::utils::great_circle::sample_great_circle_arc;
 /// Pass `[start, end]` for a simple two-point line segment.
 ///
 /// Returns a vector of unique cell IDs along the polyline, in order.
-pub fn line_string_to_cells(waypoints: &[LonLat], resolution: i32) -> Result<Vec<u64>, String> {
+pub fn line_string_to_cells(waypoints: &[LonLat], resolution: i32) -> Result<Vec<u64>, A5Error> {
     if waypoints.is_empty() {
         return Ok(Vec::new());
     }
@@ -3,11 +3,14 @@
 // Copyright (c) A5 contributors
 
 pub mod cap;
+pub mod edge;
 pub mod global_neighbors;
 pub mod grid_disk;
+pub mod grid_path;
 pub mod lattice_boundary;
 pub mod lattice_flood_fill;
 pub mod lattice_neighbors;
 pub mod line;
 pub mod neighbors;
 pub mod quintant_neighbors;
+pub mod vertex;
@@ -0,0 +1,153 @@
+// A5
+// SPDX-License-Identifier: Apache-2.0
+// Copyright (c) A5 contributors
+
+//! Cell vertices as shared entities with globally unique ids.
+//!
+//! Every vertex is owned by the cell with the smallest id among those
+//! meeting there. A vertex id holds the owner's cell id and the vertex index
+//! in the same tagged layout as a directed edge id, with its own kind bit
+//! (see `traversal::edge`), so vertex, edge and cell ids never coincide and
+//! vertices exist up to `MAX_EDGE_RESOLUTION`. The cells at a vertex are
+//! found among the vertex neighbors of the cell, which covers quintant and
+//! face seams.
+
+use std::fmt;
+
+use crate::coordinate_systems::{Cartesian, LonLat};
+use crate::core::coordinate_transforms::{to_lon_lat, to_spherical};
+use crate::core::error::A5Error;
+use crate::core::hex::u64_to_hex;
+use crate::traversal::edge::{
+    cell_vertices, check_edge_resolution, decode_cell_part, encode_cell_part, same_vertex, CellPart,
+};
+use crate::traversal::global_neighbors::get_global_cell_neighbors;
+use crate::utils::vector::vec3_distance;
+
+/// Identifier of a cell vertex, shared by all cells meeting there.
+///
+/// Obtain from `cell_to_vertices`, or from a raw id with
+/// `VertexId::try_from(u64)`, which accepts only canonical ids.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct VertexId(u64);
+
+impl VertexId {
+    /// Get the raw 64-bit vertex index
+    pub const fn get(&self) -> u64 {
+        self.0
+    }
+
+    /// Position of the vertex, see `vertex_to_lonlat`
+    pub fn lonlat(&self) -> Result<LonLat, A5Error> {
+        vertex_to_lonlat(*self)
+    }
+
+    /// Cells meeting at the vertex, see `vertex_to_cells`
+    pub fn cells(&self) -> Result<Vec<u64>, A5Error> {
+        vertex_to_cells(*self)
+    }
+
+    fn owner_and_index(&self) -> (u64, usize) {
+        decode_cell_part(self.0, CellPart::Vertex).expect("vertex ids are validated on creation")
+    }
+}
+
+impl TryFrom<u64> for VertexId {
+    type Error = A5Error;
+
+    /// Accepts only ids as returned by `cell_to_vertices`.
+    fn try_from(index: u64) -> Result<Self, Self::Error> {
+        if is_valid_vertex(index) {
+            Ok(VertexId(index))
+        } else {
+            Err(A5Error::InvalidVertex(index))
+        }
+    }
+}
+
+impl From<VertexId> for u64 {
+    fn from(vertex: VertexId) -> Self {
+        vertex.0
+    }
+}
+
+impl fmt::Display for VertexId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&u64_to_hex(self.0))
+    }
+}
+
+/// For each vertex of `cell`, the cells meeting there with the index of the
+/// vertex in each of them, sorted by cell id so the owner comes first
+fn cells_at_vertices(cell: u64) -> Result<Vec<Vec<(u64, usize)>>, A5Error> {
+    let vertices = cell_vertices(cell)?;
+    let mut meeting: Vec<Vec<(u64, usize)>> =
+        (0..vertices.len()).map(|i| vec![(cell, i)]).collect();
+    let edge_lengths: Vec<f64> = (0..vertices.len())
+        .map(|i| vec3_distance(&vertices[i], &vertices[(i + 1) % vertices.len()]))
+        .collect();
+
+    for neighbor in get_global_cell_neighbors(cell, false) {
+        let neighbor_vertices: Vec<Cartesian> = cell_vertices(neighbor)?;
+        for (i, point) in vertices.iter().enumerate() {
+            if let Some(j) = neighbor_vertices
+                .iter()
+                .position(|v| same_vertex(point, v, edge_lengths[i]))
+            {
+                meeting[i].push((neighbor, j));
+            }
+        }
+    }
+    for cells in &mut meeting {
+        cells.sort_unstable();
+    }
+    Ok(meeting)
+}
+
+/// Whether `vertex` is the canonical id of a cell vertex. Vertex ids are
+/// never valid cell or edge ids.
+pub fn is_valid_vertex(vertex: u64) -> bool {
+    let Some((owner, index)) = decode_cell_part(vertex, CellPart::Vertex) else {
+        return false;
+    };
+    match cells_at_vertices(owner) {
+        Ok(meeting) => meeting[index][0].0 == owner,
+        Err(_) => false,
+    }
+}
+
+/// Returns the vertices of a cell, in the same order as its edges: vertex
+/// `i` is where edge `i` starts.
+///
+/// Cells have 5 vertices, except resolution-1 cells which have 3. Returns
+/// `A5Error::InvalidResolution` for the world cell and for resolutions
+/// above `MAX_EDGE_RESOLUTION`.
+pub fn cell_to_vertices(cell: u64) -> Result<Vec<VertexId>, A5Error> {
+    check_edge_resolution(cell)?;
+    Ok(cells_at_vertices(cell)?
+        .into_iter()
+        .map(|cells| {
+            let (owner, index) = cells[0];
+            VertexId(encode_cell_part(owner, index, CellPart::Vertex))
+        })
+        .collect())
+}
+
+/// Returns the position of a vertex.
+pub fn vertex_to_lonlat(vertex: VertexId) -> Result<LonLat, A5Error> {
+    let (owner, index) = vertex.owner_and_index();
+    let point = cell_vertices(owner)?[index];
+    Ok(to_lon_lat(to_spherical(point)))
+}
+
+/// Returns the cells meeting at a vertex, sorted by id.
+///
+/// Three or four cells meet at most vertices and five at the dodecahedron
+/// face centres; the triangles of resolution 1 also meet six at a corner.
+pub fn vertex_to_cells(vertex: VertexId) -> Result<Vec<u64>, A5Error> {
+    let (owner, index) = vertex.owner_and_index();
+    Ok(cells_at_vertices(owner)?[index]
+        .iter()
+        .map(|&(cell, _)| cell)
+        .collect())
+}
@@ -4,7 +4,9 @@
 
 use crate::coordinate_systems::Cartesian;
 use crate::core::constants::AUTHALIC_RADIUS_EARTH;
-use crate::utils::vector::{precompute_slerp, slerp_ctx};
+use crate::utils::vector::{
+    cross, dot, length, precompute_slerp, slerp_ctx, subtract, vec3_distance,
+};
 
 /// Great-circle distance in meters between two unit vectors on the authalic sphere.
 pub fn great_circle_distance(a: Cartesian, b: Cartesian) -> f64 {
@@ -29,3 +31,24 @@ pub fn sample_great_circle_arc(a: Cartesian, b: Cartesian, sample_interval: f64)
     }
     samples
 }
+
+/// Great-circle distance in meters between two unit vectors, from their chord
+/// length. Unlike `great_circle_distance` this keeps full precision for arcs
+/// down to millimeters, where `acos` of a dot product near 1 breaks down.
+pub fn short_arc_distance(a: Cartesian, b: Cartesian) -> f64 {
+    let chord = vec3_distance(&a, &b);
+    2.0 * (chord / 2.0).min(1.0).asin() * AUTHALIC_RADIUS_EARTH
+}
+
+/// Distance in meters from `point` to the great circle through `a` and `b`,
+/// all unit vectors, or to `a` if the two coincide. Taken relative to `a`, so
+/// that nearby points do not cancel out.
+pub fn cross_track_distance(point: Cartesian, a: Cartesian, b: Cartesian) -> f64 {
+    let normal = cross(a, subtract(b, a));
+    if length(normal) == 0.0 {
+        // No great circle through coinciding points
+        return short_arc_distance(point, a);
+    }
+    let sine = dot(subtract(point, a), normal) / length(normal);
+    sine.abs().min(1.0).asin() * AUTHALIC_RADIUS_EARTH
+}
@@ -68,10 +68,19 @@ pub fn slerp_ctx(a: Cartesian, b: Cartesian, t: f64, ctx: Option<SlerpContext>)
 // Helper functions for 3D vector operations
 
 /// Compute dot product of two vectors
-fn dot(a: Cartesian, b: Cartesian) -> f64 {
+pub fn dot(a: Cartesian, b: Cartesian) -> f64 {
     a.x() * b.x() + a.y() * b.y() + a.z() * b.z()
 }
 
+/// Compute cross product of two vectors
+pub fn cross(a: Cartesian, b: Cartesian) -> Cartesian {
+    Cartesian::new(
+        a.y() * b.z() - a.z() * b.y(),
+        a.z() * b.x() - a.x() * b.z(),
+        a.x() * b.y() - a.y() * b.x(),
+    )
+}
+
 /// Compute length of a vector
 pub fn length(v: Cartesian) -> f64 {
     (v.x() * v.x() + v.y() * v.y() + v.z() * v.z()).sqrt()
@@ -92,7 +101,7 @@ fn lerp(a: Cartesian, b: Cartesian, t: f64) -> Cartesian {
 }
 
 /// Subtract two vectors
-fn subtract(a: Cartesian, b: Cartesian) -> Cartesian {
+pub fn subtract(a: Cartesian, b: Cartesian) -> Cartesian {
     Cartesian::new(a.x() - b.x(), a.y() - b.y(), a.z() - b.z())
 }
 
@@ -0,0 +1,120 @@
+// A5
+// SPDX-License-Identifier: Apache-2.0
+// Copyright (c) A5 contributors
+
+use a5::{
+    cell_to_boundary, cell_to_split_boundary, get_res0_cells, lonlat_to_cell, uncompact,
+    CellToBoundaryOptions, LonLat, Winding, WORLD_CELL,
+};
+
+mod common;
+use common::planar_area;
+
+fn open() -> Option<CellToBoundaryOptions> {
+    Some(CellToBoundaryOptions::default().with_closed_ring(false))
+}
+
+#[test]
+fn test_split_boundaries_tile_the_map() {
+    for resolution in 0..=3 {
+        let cells = uncompact(&get_res0_cells().unwrap(), resolution).unwrap();
+        let mut total = 0.0;
+        let mut split = 0;
+        for cell in cells {
+            let parts = cell_to_split_boundary(cell, open()).unwrap();
+            if parts.len() > 1 {
+                split += 1;
+            }
+            for part in parts {
+                let area = planar_area(&part);
+                assert!(area > 0.0, "cell {:x} has a CW or empty part", cell);
+                total += area;
+                for point in part {
+                    assert!((-180.0..=180.0).contains(&point.longitude()));
+                    assert!((-90.0..=90.0).contains(&point.latitude()));
+                }
+            }
+        }
+        assert!(split > 0);
+        assert!(
+            (total - 360.0 * 180.0).abs() < 1e-6,
+            "res {} area {}",
+            resolution,
+            total
+        );
+    }
+}
+
+#[test]
+fn test_antimeridian_cell_is_split() {
+    let cell = lonlat_to_cell(LonLat::new(179.99, 10.8), 5).unwrap();
+    assert!(cell_to_boundary(cell, None)
+        .unwrap()
+        .iter()
+        .any(|point| point.longitude() > 180.0));
+
+    let parts = cell_to_split_boundary(cell, None).unwrap();
+    assert_eq!(parts.len(), 2);
+    let east_of = |part: &[LonLat], longitude: f64| part.iter().all(|p| p.longitude() >= longitude);
+    let west_of = |part: &[LonLat], longitude: f64| part.iter().all(|p| p.longitude() <= longitude);
+    assert!(east_of(&parts[0], 0.0) && west_of(&parts[1], 0.0));
+    for part in &parts {
+        assert_eq!(part.first(), part.last());
+        assert!(part.iter().any(|p| p.longitude().abs() == 180.0));
+    }
+
+    let options = CellToBoundaryOptions::default().with_winding(Winding::Clockwise);
+    for part in cell_to_split_boundary(cell, Some(options)).unwrap() {
+        assert!(planar_area(&part) < 0.0);
+    }
+}
+
+#[test]
+fn test_pole_cells() {
+    // The resolution-0 faces at the poles contain them and run all the way
+    // around the map
+    for latitude in [90.0, -90.0] {
+        let face = lonlat_to_cell(LonLat::new(0.0, latitude), 0).unwrap();
+        let parts = cell_to_split_boundary(face, open()).unwrap();
+        assert_eq!(parts.len(), 1);
+        let ring = &parts[0];
+        assert_eq!(ring.iter().filter(|p| p.latitude() == latitude).count(), 2);
+        assert!(ring.iter().any(|p| p.longitude() == -180.0));
+        assert!(ring.iter().any(|p| p.longitude() == 180.0));
+    }
+
+    // Finer cells meet at the poles, and their pole vertex is spread along
+    // the pole's edge of the map
+    for resolution in [1, 2, 9] {
+        let cell = lonlat_to_cell(LonLat::new(36.0, 89.9999), resolution).unwrap();
+        let boundary = cell_to_boundary(cell, open()).unwrap();
+        assert_eq!(boundary.iter().filter(|p| p.latitude() == 90.0).count(), 1);
+
+        let parts = cell_to_split_boundary(cell, open()).unwrap();
+        assert_eq!(parts.len(), 1);
+        let ring = &parts[0];
+        assert_eq!(ring.len(), boundary.len() + 1);
+        let pole: Vec<&LonLat> = ring.iter().filter(|p| p.latitude() == 90.0).collect();
+        assert_eq!(pole.len(), 2);
+        assert!((pole[0].longitude() - pole[1].longitude()).abs() > 1.0);
+    }
+}
+
+#[test]
+fn test_other_cells_are_unchanged() {
+    for (lon, lat) in [(10.0, 45.0), (-73.98, 40.75), (151.2, -33.9)] {
+        for resolution in [3, 12, 30] {
+            let cell = lonlat_to_cell(LonLat::new(lon, lat), resolution).unwrap();
+            assert_eq!(
+                cell_to_split_boundary(cell, None).unwrap(),
+                vec![cell_to_boundary(cell, None).unwrap()]
+            );
+        }
+    }
+}
+
+#[test]
+fn test_world_and_invalid_cells() {
+    assert!(cell_to_split_boundary(WORLD_CELL, None).unwrap().is_empty());
+    assert!(cell_to_split_boundary(63 << 58 | 1 << 56, None).is_err());
+}
@@ -1,14 +1,21 @@
 use a5::coordinate_systems::LonLat;
 use a5::core::cell::{
-    a5cell_contains_point, cell_to_boundary, cell_to_lonlat, lonlat_to_cell, CellToBoundaryOptions,
+    a5cell_contains_point, cell_to_boundary, cell_to_lonlat, lonlat_to_cell, BoundaryStart,
+    CellToBoundaryOptions, Winding,
 };
-use a5::core::coordinate_transforms::from_lon_lat;
+use a5::core::coordinate_transforms::{from_lon_lat, to_cartesian};
+use a5::core::error::A5Error;
 use a5::core::hex::hex_to_u64;
 use a5::core::serialization::{deserialize, MAX_RESOLUTION};
+use a5::traversal::vertex::{cell_to_vertices, vertex_to_lonlat};
+use a5::utils::great_circle::{cross_track_distance, short_arc_distance};
 use approx::assert_relative_eq;
 use serde::Deserialize;
 use std::collections::HashMap;
 
+mod common;
+use common::planar_area;
+
 #[derive(Deserialize)]
 struct GeoJSONGeometry {
     coordinates: Vec<f64>,
@@ -66,6 +73,7 @@ fn test_antimeridian_cell_longitude_span() {
             let options = CellToBoundaryOptions {
                 closed_ring: true,
                 segments: Some(segment),
+                ..Default::default()
             };
             let boundary =
                 cell_to_boundary(cell_id_u64, Some(options)).expect("Failed to get boundary");
@@ -283,3 +291,192 @@ fn test_cell_to_lonlat_matches_fixture_values() {
         assert_relative_eq!(lonlat.latitude(), fixture.center_lonlat[1], epsilon = 1e-10);
     }
 }
+
+/// Ring from the start corner, without repeating it at the end
+fn boundary_with(cell: u64, options: CellToBoundaryOptions) -> Result<Vec<LonLat>, A5Error> {
+    let mut ring = cell_to_boundary(cell, Some(options.with_closed_ring(true)))?;
+    ring.pop();
+    Ok(ring)
+}
+
+#[test]
+fn test_cell_boundary_winding() {
+    for resolution in [0, 1, 2, 7, 15] {
+        let cell = lonlat_to_cell(LonLat::new(-31.7, 28.3), resolution).unwrap();
+        let options = |winding| {
+            CellToBoundaryOptions::default()
+                .with_segments(3)
+                .with_winding(winding)
+        };
+        let ccw = boundary_with(cell, options(Winding::CounterClockwise)).unwrap();
+        let cw = boundary_with(cell, options(Winding::Clockwise)).unwrap();
+        assert!(planar_area(&ccw) > 0.0);
+        assert!(planar_area(&cw) < 0.0);
+
+        // Both start from vertex 0 and run in opposite directions
+        assert_eq!(ccw[0], cw[0]);
+        let mut reversed = cw.clone();
+        reversed[1..].reverse();
+        assert_eq!(ccw, reversed);
+        if resolution > 0 {
+            let first = vertex_to_lonlat(cell_to_vertices(cell).unwrap()[0]).unwrap();
+            assert!((first.longitude() - ccw[0].longitude()).abs() < 1e-9);
+            assert!((first.latitude() - ccw[0].latitude()).abs() < 1e-9);
+        }
+    }
+}
+
+#[test]
+fn test_cell_boundary_open_ring() {
+    // An open ring is the closed ring without its first point, so it ends at
+    // the start corner, as it did before the winding and start options
+    let cell = lonlat_to_cell(LonLat::new(-31.7, 28.3), 7).unwrap();
+    for winding in [Winding::CounterClockwise, Winding::Clockwise] {
+        for start in [
+            BoundaryStart::FirstVertex,
+            BoundaryStart::SmallestCoordinate,
+        ] {
+            let options = CellToBoundaryOptions::default()
+                .with_segments(3)
+                .with_winding(winding)
+                .with_start(start);
+            let closed = cell_to_boundary(cell, Some(options.clone())).unwrap();
+            let open = cell_to_boundary(cell, Some(options.with_closed_ring(false))).unwrap();
+            assert_eq!(closed.first(), closed.last());
+            assert_eq!(open[..], closed[1..]);
+        }
+    }
+
+    // The default open ring runs backwards through the pentagon vertices,
+    // from the last one to vertex 0
+    let corners = cell_to_boundary(
+        cell,
+        Some(
+            CellToBoundaryOptions::default()
+                .with_closed_ring(false)
+                .with_segments(1),
+        ),
+    )
+    .unwrap();
+    let vertices: Vec<LonLat> = cell_to_vertices(cell)
+        .unwrap()
+        .into_iter()
+        .rev()
+        .map(|vertex| vertex_to_lonlat(vertex).unwrap())
+        .collect();
+    assert_eq!(corners.len(), vertices.len());
+    for (corner, vertex) in corners.iter().zip(&vertices) {
+        assert!((corner.longitude() - vertex.longitude()).abs() < 1e-9);
+        assert!((corner.latitude() - vertex.latitude()).abs() < 1e-9);
+    }
+}
+
+#[test]
+fn test_cell_boundary_start() {
+    let cell = lonlat_to_cell(LonLat::new(151.2, -33.9), 9).unwrap();
+    let options = |winding| {
+        CellToBoundaryOptions::default()
+            .with_segments(4)
+            .with_winding(winding)
+            .with_start(BoundaryStart::SmallestCoordinate)
+    };
+    let ccw = boundary_with(cell, options(Winding::CounterClockwise)).unwrap();
+    let cw = boundary_with(cell, options(Winding::Clockwise)).unwrap();
+
+    // The same corner comes first whatever the winding
+    assert_eq!(ccw[0], cw[0]);
+    let corners = boundary_with(cell, CellToBoundaryOptions::default().with_segments(1)).unwrap();
+    for corner in &corners {
+        assert!((ccw[0].longitude(), ccw[0].latitude()) <= (corner.longitude(), corner.latitude()));
+    }
+
+    // A rotation of the default ring, identical on every call
+    let default = boundary_with(cell, CellToBoundaryOptions::default().with_segments(4)).unwrap();
+    let offset = default.iter().position(|&p| p == ccw[0]).unwrap();
+    assert_eq!(offset % 4, 0);
+    let mut rotated = default.clone();
+    rotated.rotate_left(offset);
+    assert_eq!(rotated, ccw);
+    assert_eq!(
+        boundary_with(cell, options(Winding::CounterClockwise)).unwrap(),
+        ccw
+    );
+}
+
+fn segment_lengths(ring: &[LonLat]) -> Vec<f64> {
+    let points: Vec<_> = ring
+        .iter()
+        .map(|&p| to_cartesian(from_lon_lat(p)))
+        .collect();
+    (0..points.len())
+        .map(|i| short_arc_distance(points[i], points[(i + 1) % points.len()]))
+        .collect()
+}
+
+#[test]
+fn test_cell_boundary_max_segment_length() {
+    for (resolution, max_length) in [(2, 50_000.0), (5, 1000.0), (12, 10.0)] {
+        let cell = lonlat_to_cell(LonLat::new(-31.7, 58.3), resolution).unwrap();
+        let options = CellToBoundaryOptions::default()
+            .with_max_segment_length_m(max_length)
+            // Ignored in favour of the tolerance
+            .with_segments(1);
+        let boundary = boundary_with(cell, options).unwrap();
+        assert!(segment_lengths(&boundary).iter().all(|&l| l <= max_length));
+
+        // One segment fewer per edge would be too long
+        let segments = boundary.len() / 5;
+        assert!(segments > 1);
+        let coarser = CellToBoundaryOptions::default().with_segments(segments as i32 - 1);
+        let coarser = boundary_with(cell, coarser).unwrap();
+        assert!(segment_lengths(&coarser).iter().any(|&l| l > max_length));
+    }
+}
+
+#[test]
+fn test_cell_boundary_max_deviation() {
+    for (lon, lat) in [(10.0, 20.0), (36.0, 89.999)] {
+        for (resolution, max_deviation) in [(3, 100.0), (6, 1.0), (12, 0.001)] {
+            let cell = lonlat_to_cell(LonLat::new(lon, lat), resolution).unwrap();
+            let options = CellToBoundaryOptions::default().with_max_deviation_m(max_deviation);
+            let boundary = boundary_with(cell, options).unwrap();
+            let segments = boundary.len() / 5;
+
+            // Compare against the true edge, sampled four times as densely
+            let finer = CellToBoundaryOptions::default().with_segments(4 * segments as i32);
+            let finer: Vec<_> = boundary_with(cell, finer)
+                .unwrap()
+                .iter()
+                .map(|&p| to_cartesian(from_lon_lat(p)))
+                .collect();
+            let n = finer.len();
+            for start in (0..n).step_by(4) {
+                let (a, b) = (finer[start], finer[(start + 4) % n]);
+                for offset in 1..4 {
+                    let deviation = cross_track_distance(finer[start + offset], a, b);
+                    assert!(deviation <= 1.01 * max_deviation, "{}", deviation);
+                }
+            }
+        }
+    }
+
+    // The edges of resolution-0 faces are great circle arcs already
+    let face = lonlat_to_cell(LonLat::new(10.0, 20.0), 0).unwrap();
+    let options = CellToBoundaryOptions::default().with_max_deviation_m(0.001);
+    assert_eq!(boundary_with(face, options).unwrap().len(), 5);
+}
+
+#[test]
+fn test_cell_boundary_invalid_tolerance() {
+    let cell = lonlat_to_cell(LonLat::new(10.0, 20.0), 5).unwrap();
+    for tolerance in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+        let length = CellToBoundaryOptions::default().with_max_segment_length_m(tolerance);
+        let deviation = CellToBoundaryOptions::default().with_max_deviation_m(tolerance);
+        for options in [length, deviation] {
+            assert!(matches!(
+                cell_to_boundary(cell, Some(options)),
+                Err(A5Error::InvalidTolerance(_))
+            ));
+        }
+    }
+}
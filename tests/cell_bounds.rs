@@ -0,0 +1,138 @@
+// A5
+// SPDX-License-Identifier: Apache-2.0
+// Copyright (c) A5 contributors
+
+use a5::{
+    cell_to_bbox, cell_to_boundary, cell_to_bounding_cap, cell_to_lonlat, cells_to_bbox,
+    get_res0_cells, grid_disk, lonlat_to_cell, uncompact, BoundingBox, CellToBoundaryOptions,
+    LonLat, WORLD_CELL,
+};
+
+/// Boundary sampled far more densely than the bounds themselves use
+fn dense_boundary(cell: u64) -> Vec<LonLat> {
+    cell_to_boundary(
+        cell,
+        Some(CellToBoundaryOptions::default().with_segments(2048)),
+    )
+    .unwrap()
+    .into_iter()
+    .map(|p| {
+        LonLat::new(
+            p.longitude() - 360.0 * ((p.longitude() + 180.0) / 360.0).floor(),
+            p.latitude(),
+        )
+    })
+    .collect()
+}
+
+#[test]
+fn test_bbox_contains_curved_edges() {
+    let mut cells = uncompact(&get_res0_cells().unwrap(), 2).unwrap();
+    for (lon, lat) in [(10.0, 45.0), (179.99, 10.8), (-120.0, -89.0), (36.0, 89.9)] {
+        for resolution in [4, 9, 16] {
+            cells.push(lonlat_to_cell(LonLat::new(lon, lat), resolution).unwrap());
+        }
+    }
+    for cell in cells {
+        let bbox = cell_to_bbox(cell).unwrap();
+        assert!(bbox.contains(cell_to_lonlat(cell).unwrap()));
+        for point in dense_boundary(cell) {
+            assert!(
+                bbox.contains(point),
+                "cell {:x} {:?} {:?}",
+                cell,
+                bbox,
+                point
+            );
+        }
+    }
+}
+
+#[test]
+fn test_bbox_is_tight() {
+    let cell = lonlat_to_cell(LonLat::new(10.0, 45.0), 9).unwrap();
+    let bbox = cell_to_bbox(cell).unwrap();
+    let boundary = dense_boundary(cell);
+    let min = |f: fn(&LonLat) -> f64| boundary.iter().map(f).fold(f64::MAX, f64::min);
+    let max = |f: fn(&LonLat) -> f64| boundary.iter().map(f).fold(f64::MIN, f64::max);
+    let height = max(LonLat::latitude) - min(LonLat::latitude);
+    assert!(!bbox.crosses_antimeridian());
+    assert!(min(LonLat::longitude) - bbox.west < 0.05 * height);
+    assert!(bbox.east - max(LonLat::longitude) < 0.05 * height);
+    assert!(min(LonLat::latitude) - bbox.south < 0.05 * height);
+    assert!(bbox.north - max(LonLat::latitude) < 0.05 * height);
+}
+
+#[test]
+fn test_bbox_across_antimeridian_and_poles() {
+    let cell = lonlat_to_cell(LonLat::new(179.99, 10.8), 5).unwrap();
+    let bbox = cell_to_bbox(cell).unwrap();
+    assert!(bbox.crosses_antimeridian());
+    assert!(bbox.west > 170.0 && bbox.east < -170.0);
+    assert!(bbox.contains(LonLat::new(180.0, 10.8)));
+    assert!(!bbox.contains(LonLat::new(0.0, 10.8)));
+
+    for latitude in [90.0, -90.0] {
+        let cell = lonlat_to_cell(LonLat::new(36.0, latitude), 6).unwrap();
+        let bbox = cell_to_bbox(cell).unwrap();
+        assert_eq!((bbox.west, bbox.east), (-180.0, 180.0));
+        assert!(bbox.north == 90.0 || bbox.south == -90.0);
+    }
+}
+
+#[test]
+fn test_cells_to_bbox() {
+    let center = lonlat_to_cell(LonLat::new(179.9, -16.5), 7).unwrap();
+    let disk = grid_disk(center, 3).unwrap();
+    let bbox = cells_to_bbox(&disk).unwrap().unwrap();
+    assert!(bbox.crosses_antimeridian());
+    for &cell in &disk {
+        let cell_bbox = cell_to_bbox(cell).unwrap();
+        assert!(bbox.south <= cell_bbox.south && bbox.north >= cell_bbox.north);
+        for point in dense_boundary(cell) {
+            assert!(bbox.contains(point));
+        }
+    }
+
+    let far = lonlat_to_cell(LonLat::new(10.0, 45.0), 7).unwrap();
+    let near = lonlat_to_cell(LonLat::new(12.0, 44.0), 7).unwrap();
+    let bbox = cells_to_bbox(&[far, near]).unwrap().unwrap();
+    assert!(!bbox.crosses_antimeridian());
+    assert!(bbox.west < 10.0 && bbox.east > 12.0 && bbox.east - bbox.west < 3.0);
+
+    assert_eq!(cells_to_bbox(&[]).unwrap(), None);
+    assert_eq!(
+        cells_to_bbox(&[WORLD_CELL]).unwrap(),
+        Some(BoundingBox::WORLD)
+    );
+}
+
+#[test]
+fn test_bounding_cap_contains_cell() {
+    for (lon, lat) in [(10.0, 45.0), (179.99, 10.8), (36.0, 90.0)] {
+        for resolution in [0, 5, 12, 20, 28] {
+            let cell = lonlat_to_cell(LonLat::new(lon, lat), resolution).unwrap();
+            let cap = cell_to_bounding_cap(cell).unwrap();
+            assert!(cap.contains_lonlat(cell_to_lonlat(cell).unwrap()));
+            for point in dense_boundary(cell) {
+                assert!(cap.contains_lonlat(point), "cell {:x}", cell);
+            }
+            let center = cap.center_lonlat();
+            let far = LonLat::new(center.longitude() + 180.0, -center.latitude());
+            assert!(!cap.contains_lonlat(far));
+        }
+    }
+}
+
+#[test]
+fn test_world_and_invalid_cells() {
+    assert_eq!(cell_to_bbox(WORLD_CELL).unwrap(), BoundingBox::WORLD);
+    assert!(cell_to_bounding_cap(WORLD_CELL)
+        .unwrap()
+        .contains_lonlat(LonLat::new(0.0, -90.0)));
+
+    let invalid = 63 << 58 | 1 << 56;
+    assert!(cell_to_bbox(invalid).is_err());
+    assert!(cells_to_bbox(&[invalid]).is_err());
+    assert!(cell_to_bounding_cap(invalid).is_err());
+}
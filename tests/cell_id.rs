@@ -0,0 +1,110 @@
+// A5
+// SPDX-License-Identifier: Apache-2.0
+// Copyright (c) A5 contributors
+
+use a5::{
+    cell_to_boundary, cell_to_children, cell_to_lonlat, cell_to_parent, grid_disk, lonlat_to_cell,
+    uncompact, A5Error, CellId, InvalidCellReason, LonLat, WORLD_CELL,
+};
+use std::collections::HashSet;
+
+#[test]
+fn test_try_from_accepts_valid_cells() {
+    let cell = lonlat_to_cell(LonLat::new(-73.9857, 40.7484), 10).unwrap();
+    let cell_id = CellId::try_from(cell).unwrap();
+    assert_eq!(cell_id.get(), cell);
+    assert_eq!(u64::from(cell_id), cell);
+    assert_eq!(CellId::try_from(WORLD_CELL).unwrap(), CellId::WORLD);
+    for res in [0, 1, 2, 15, 29, 30] {
+        let cell = lonlat_to_cell(LonLat::new(151.2, -33.9), res).unwrap();
+        assert!(CellId::try_from(cell).is_ok(), "res {}", res);
+    }
+}
+
+#[test]
+fn test_try_from_rejects_invalid_cells() {
+    // Origin/segment out of range ("Could not parse origin")
+    assert_eq!(
+        CellId::try_from(0xfe00000000000000),
+        Err(A5Error::InvalidCell {
+            cell: 0xfe00000000000000,
+            reason: InvalidCellReason::Origin
+        })
+    );
+    // Lowest set bit at an even position is not a resolution marker
+    let cell = lonlat_to_cell(LonLat::new(0.0, 0.0), 5).unwrap();
+    let corrupt = cell | (1 << 6);
+    assert_eq!(
+        CellId::try_from(corrupt),
+        Err(A5Error::InvalidCell {
+            cell: corrupt,
+            reason: InvalidCellReason::ResolutionMarker
+        })
+    );
+}
+
+#[test]
+fn test_hex_round_trip() {
+    let cell = lonlat_to_cell(LonLat::new(2.35, 48.85), 12).unwrap();
+    let cell_id = CellId::try_from(cell).unwrap();
+    let hex = cell_id.to_string();
+    assert_eq!(hex, a5::u64_to_hex(cell));
+    assert_eq!(hex.parse::<CellId>().unwrap(), cell_id);
+    assert!(matches!(
+        "zz".parse::<CellId>(),
+        Err(A5Error::InvalidHex(_))
+    ));
+}
+
+#[test]
+fn test_methods_wrap_free_functions() {
+    let lonlat = LonLat::new(-0.1276, 51.5072);
+    let cell_id = CellId::from_lonlat(lonlat, 8).unwrap();
+    let cell = cell_id.get();
+
+    assert_eq!(cell_id.resolution(), 8);
+    assert_eq!(
+        cell_id.parent(None).unwrap().get(),
+        cell_to_parent(cell, None).unwrap()
+    );
+    assert_eq!(cell_id.parent(Some(3)).unwrap().resolution(), 3);
+    let children: Vec<u64> = cell_id
+        .children(Some(10))
+        .unwrap()
+        .into_iter()
+        .map(u64::from)
+        .collect();
+    assert_eq!(children, cell_to_children(cell, Some(10)).unwrap());
+    assert_eq!(cell_id.center().unwrap(), cell_to_lonlat(cell).unwrap());
+    assert_eq!(
+        cell_id.boundary(None).unwrap(),
+        cell_to_boundary(cell, None).unwrap()
+    );
+
+    let neighbors: HashSet<u64> = cell_id.neighbors().into_iter().map(u64::from).collect();
+    let disk: HashSet<u64> = uncompact(&grid_disk(cell, 1).unwrap(), 8)
+        .unwrap()
+        .into_iter()
+        .collect();
+    assert_eq!(neighbors.len(), 5);
+    assert!(neighbors.iter().all(|n| disk.contains(n)));
+    assert!(cell_id.vertex_neighbors().len() > neighbors.len());
+}
+
+#[test]
+fn test_ordering_follows_index() {
+    let mut cells = CellId::WORLD.children(Some(1)).unwrap();
+    cells.reverse();
+    cells.sort();
+    assert!(cells.windows(2).all(|w| w[0].get() < w[1].get()));
+}
+
+#[test]
+fn test_world_cell_has_no_neighbors() {
+    let world = CellId::try_from(WORLD_CELL).unwrap();
+    assert!(world.neighbors().is_empty());
+    assert!(world.vertex_neighbors().is_empty());
+    for cell in CellId::WORLD.children(Some(0)).unwrap() {
+        assert_eq!(cell.neighbors().len(), 5);
+    }
+}
@@ -119,6 +119,7 @@ fn test_every_boundary_edge_of_test_cells_is_within_10_percent_of_average() {
             Some(CellToBoundaryOptions {
                 closed_ring: true,
                 segments: Some(SEGMENTS as i32),
+                ..Default::default()
             }),
         )
         .unwrap();
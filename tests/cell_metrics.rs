@@ -0,0 +1,128 @@
+// A5
+// SPDX-License-Identifier: Apache-2.0
+// Copyright (c) A5 contributors
+
+use std::f64::consts::PI;
+
+use a5::{
+    cell_area, cell_area_exact, cell_edge_length_avg, cell_edge_lengths, cell_perimeter,
+    cell_shape_metrics, cell_to_children, get_res0_cells, lonlat_to_cell, A5Error, LonLat,
+    WORLD_CELL,
+};
+
+const POINTS: [(f64, f64); 5] = [
+    (0.0, 0.0),
+    (-73.98, 40.75),
+    (151.2, -33.9),
+    (179.9, 80.0),
+    (36.0, 90.0),
+];
+
+#[test]
+fn test_cells_are_equal_area() {
+    for resolution in [0, 1, 2, 4, 9, 16, 23, 29, 30] {
+        for (lon, lat) in POINTS {
+            // The inverse projection runs out of precision at the face centre
+            if lat == 90.0 && resolution > 16 {
+                continue;
+            }
+            let cell = lonlat_to_cell(LonLat::new(lon, lat), resolution).unwrap();
+            let metrics = cell_shape_metrics(cell).unwrap();
+            assert!(
+                (metrics.area_ratio - 1.0).abs() < 1e-6,
+                "res {} ratio {}",
+                resolution,
+                metrics.area_ratio
+            );
+            assert_eq!(metrics.area, cell_area_exact(cell).unwrap());
+        }
+    }
+
+    let total: f64 = get_res0_cells()
+        .unwrap()
+        .iter()
+        .map(|&cell| cell_area_exact(cell).unwrap())
+        .sum();
+    assert!((total / cell_area(-1) - 1.0).abs() < 1e-12);
+}
+
+#[test]
+fn test_children_partition_parent_area() {
+    let parent = lonlat_to_cell(LonLat::new(-31.7, 58.3), 3).unwrap();
+    let children = cell_to_children(parent, Some(5)).unwrap();
+    let total: f64 = children
+        .iter()
+        .map(|&cell| cell_area_exact(cell).unwrap())
+        .sum();
+    assert!((total / cell_area_exact(parent).unwrap() - 1.0).abs() < 1e-7);
+}
+
+#[test]
+fn test_edges_and_perimeter() {
+    for resolution in [0, 1, 3, 12, 30] {
+        let cell = lonlat_to_cell(LonLat::new(10.0, 20.0), resolution).unwrap();
+        let edges = cell_edge_lengths(cell).unwrap();
+        assert_eq!(edges.len(), if resolution == 1 { 3 } else { 5 });
+
+        let perimeter = cell_perimeter(cell).unwrap();
+        assert!((perimeter - edges.iter().sum::<f64>()).abs() < 1e-9 * perimeter);
+        let average = perimeter / edges.len() as f64;
+        if resolution >= 2 {
+            assert!((average / cell_edge_length_avg(resolution) - 1.0).abs() < 0.1);
+        }
+        for edge in edges {
+            assert!(edge > 0.7 * average && edge < 1.3 * average);
+        }
+    }
+}
+
+#[test]
+fn test_shape_metrics() {
+    for resolution in [5, 10, 16] {
+        for (lon, lat) in POINTS {
+            let cell = lonlat_to_cell(LonLat::new(lon, lat), resolution).unwrap();
+            let metrics = cell_shape_metrics(cell).unwrap();
+            assert_eq!(metrics.edge_lengths, cell_edge_lengths(cell).unwrap());
+            assert_eq!(metrics.perimeter, cell_perimeter(cell).unwrap());
+            assert!(metrics.compactness > 0.6 && metrics.compactness < 0.865);
+
+            // Pentagons, close to flat at these sizes except at the face
+            // centre (36°, 90°), where the projection bends the edges
+            assert_eq!(metrics.interior_angles.len(), 5);
+            assert!(metrics.interior_angles.iter().all(|&a| a > 0.0 && a < PI));
+            let sum: f64 = metrics.interior_angles.iter().sum();
+            let tolerance = if lat == 90.0 { 0.25 } else { 0.05 };
+            assert!(
+                (sum - 3.0 * PI).abs() < tolerance,
+                "res {} sum {}",
+                resolution,
+                sum
+            );
+        }
+    }
+
+    // Dodecahedron faces are regular spherical pentagons with 120° angles
+    let face = get_res0_cells().unwrap()[0];
+    let metrics = cell_shape_metrics(face).unwrap();
+    for angle in metrics.interior_angles {
+        assert!((angle - 2.0 * PI / 3.0).abs() < 1e-6);
+    }
+}
+
+#[test]
+fn test_world_and_invalid_cells() {
+    // The world cell has no boundary to measure
+    let degenerate = |result: Result<(), A5Error>| {
+        assert!(matches!(result, Err(A5Error::DegenerateGeometry(_))))
+    };
+    degenerate(cell_area_exact(WORLD_CELL).map(drop));
+    degenerate(cell_perimeter(WORLD_CELL).map(drop));
+    degenerate(cell_edge_lengths(WORLD_CELL).map(drop));
+    degenerate(cell_shape_metrics(WORLD_CELL).map(drop));
+
+    let invalid = 63 << 58 | 1 << 56;
+    assert!(cell_area_exact(invalid).is_err());
+    assert!(cell_perimeter(invalid).is_err());
+    assert!(cell_edge_lengths(invalid).is_err());
+    assert!(cell_shape_metrics(invalid).is_err());
+}
@@ -0,0 +1,203 @@
+// A5
+// SPDX-License-Identifier: Apache-2.0
+// Copyright (c) A5 contributors
+
+use std::collections::HashSet;
+
+use a5::core::compact::{compact, uncompact};
+use a5::core::serialization::{cell_to_children, cell_to_parent, get_res0_cells, WORLD_CELL};
+use a5::CellSet;
+
+const RESOLUTION: i32 = 6;
+
+/// Deterministic pseudo-random subset of `cells`, keeping about one in `ratio`
+fn sample(cells: &[u64], ratio: u64, seed: u64) -> Vec<u64> {
+    let mut state = seed;
+    cells
+        .iter()
+        .copied()
+        .filter(|_| {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (state >> 33) % ratio == 0
+        })
+        .collect()
+}
+
+/// Mixed-resolution test set: some whole res-4 cells plus scattered res-6 cells
+fn mixed_set(region: u64, seed: u64) -> Vec<u64> {
+    let coarse = cell_to_children(region, Some(4)).unwrap();
+    let mut cells = sample(&coarse, 4, seed);
+    let fine = cell_to_children(region, Some(RESOLUTION)).unwrap();
+    cells.extend(sample(&fine, 3, seed + 1));
+    cells
+}
+
+fn leaves(cells: &[u64]) -> HashSet<u64> {
+    uncompact(cells, RESOLUTION).unwrap().into_iter().collect()
+}
+
+fn from_leaves(leaves: &HashSet<u64>) -> CellSet {
+    CellSet::from_cells(&leaves.iter().copied().collect::<Vec<_>>()).unwrap()
+}
+
+/// A resolution-29 cell in `quintant` and its resolution-30 children
+fn res30_children(quintant: u64, s: u64) -> (u64, Vec<u64>) {
+    let parent = quintant << 58 | s << 2 | 0b10;
+    (parent, cell_to_children(parent, Some(30)).unwrap())
+}
+
+#[test]
+fn test_single_cells_are_preserved() {
+    let mut cells = vec![WORLD_CELL];
+    cells.extend(get_res0_cells().unwrap());
+    let mut cell = cell_to_children(get_res0_cells().unwrap()[7], Some(1)).unwrap()[3];
+    for resolution in 2..=30 {
+        cell = cell_to_children(cell, Some(resolution)).unwrap()[resolution as usize % 4];
+        cells.push(cell);
+    }
+    for cell in cells {
+        assert_eq!(CellSet::from_cells(&[cell]).unwrap().cells(), &[cell]);
+    }
+}
+
+#[test]
+fn test_siblings_compact_to_parent() {
+    // Origins, quintants and Hilbert levels
+    assert_eq!(
+        CellSet::from_cells(&get_res0_cells().unwrap())
+            .unwrap()
+            .cells(),
+        &[WORLD_CELL]
+    );
+    let origin = get_res0_cells().unwrap()[5];
+    assert_eq!(
+        CellSet::from_cells(&cell_to_children(origin, None).unwrap())
+            .unwrap()
+            .cells(),
+        &[origin]
+    );
+    let cell = cell_to_children(origin, Some(8)).unwrap()[1234];
+    assert_eq!(
+        CellSet::from_cells(&cell_to_children(cell, Some(11)).unwrap())
+            .unwrap()
+            .cells(),
+        &[cell]
+    );
+
+    // Resolution 30 uses a different marker in each quintant range
+    for quintant in [5, 35, 41] {
+        let (parent, children) = res30_children(quintant, 0x1234_5678);
+        assert_eq!(children.len(), 4);
+        let set = CellSet::from_cells(&children).unwrap();
+        assert_eq!(set.cells(), &[parent]);
+        let partial = CellSet::from_cells(&children[1..]).unwrap();
+        assert_eq!(partial.len(), 3);
+        assert!(!partial.contains(parent));
+        assert!(children[1..].iter().all(|&c| partial.contains(c)));
+        assert!(!partial.contains(children[0]));
+    }
+}
+
+#[test]
+fn test_from_cells_matches_compact() {
+    let region = cell_to_children(WORLD_CELL, Some(2)).unwrap()[123];
+    for seed in 0..5 {
+        let mut cells = mixed_set(region, seed);
+        // Overlaps and duplicates are absorbed
+        cells.push(cell_to_parent(cells[0], Some(3)).unwrap());
+        cells.push(cells[1]);
+
+        let set = CellSet::from_cells(&cells).unwrap();
+        let mut expected = compact(&uncompact(&cells, RESOLUTION).unwrap()).unwrap();
+        let mut actual = set.cells().to_vec();
+        expected.sort_unstable();
+        actual.sort_unstable();
+        assert_eq!(actual, expected);
+        assert_eq!(leaves(set.cells()), leaves(&cells));
+    }
+}
+
+#[test]
+fn test_set_operations_match_leaf_sets() {
+    let region = cell_to_children(WORLD_CELL, Some(2)).unwrap()[221];
+    for seed in 0..5 {
+        let a_cells = mixed_set(region, seed * 2);
+        let b_cells = mixed_set(region, seed * 2 + 1);
+        let a = CellSet::from_cells(&a_cells).unwrap();
+        let b = CellSet::from_cells(&b_cells).unwrap();
+        let (a_leaves, b_leaves) = (leaves(&a_cells), leaves(&b_cells));
+
+        assert_eq!(a.union(&b), from_leaves(&(&a_leaves | &b_leaves)));
+        assert_eq!(a.intersection(&b), from_leaves(&(&a_leaves & &b_leaves)));
+        assert_eq!(a.difference(&b), from_leaves(&(&a_leaves - &b_leaves)));
+        assert_eq!(b.difference(&a), from_leaves(&(&b_leaves - &a_leaves)));
+        assert_eq!(
+            a.symmetric_difference(&b),
+            from_leaves(&(&a_leaves ^ &b_leaves))
+        );
+        assert_eq!(a.intersects(&b), !a_leaves.is_disjoint(&b_leaves));
+
+        // Results stay compacted
+        let union = a.union(&b);
+        assert_eq!(
+            CellSet::from_cells(union.cells()).unwrap().cells(),
+            union.cells()
+        );
+    }
+}
+
+#[test]
+fn test_operations_with_empty_and_world() {
+    let region = cell_to_children(WORLD_CELL, Some(2)).unwrap()[42];
+    let a = CellSet::from_cells(&mixed_set(region, 7)).unwrap();
+    let empty = CellSet::new();
+    let world = CellSet::from_cells(&[WORLD_CELL]).unwrap();
+
+    assert_eq!(a.union(&empty), a);
+    assert!(a.intersection(&empty).is_empty());
+    assert_eq!(a.difference(&empty), a);
+    assert!(!a.intersects(&empty));
+
+    assert_eq!(a.union(&world), world);
+    assert_eq!(a.intersection(&world), a);
+    assert!(a.difference(&world).is_empty());
+    assert!(a.intersects(&world));
+
+    // Cutting one leaf out of the world leaves every other cell
+    let (_, children) = res30_children(37, 99);
+    let holed = world.difference(&CellSet::from_cells(&children[..1]).unwrap());
+    assert!(!holed.contains(children[0]));
+    assert!(holed.contains(children[1]));
+    assert!(!holed.contains(WORLD_CELL));
+    assert_eq!(holed.union(&world), world);
+    assert_eq!(holed.symmetric_difference(&world).cells(), &children[..1]);
+}
+
+#[test]
+fn test_contains() {
+    let region = cell_to_children(WORLD_CELL, Some(2)).unwrap()[200];
+    let cell = cell_to_children(region, Some(5)).unwrap()[17];
+    let set = CellSet::from_cells(&[cell]).unwrap();
+
+    assert!(set.contains(cell));
+    for child in cell_to_children(cell, Some(9)).unwrap() {
+        assert!(set.contains(child));
+    }
+    assert!(!set.contains(region));
+    assert!(!set.contains(cell_to_parent(cell, Some(4)).unwrap()));
+    for sibling in cell_to_children(cell_to_parent(cell, None).unwrap(), None).unwrap() {
+        assert_eq!(set.contains(sibling), sibling == cell);
+    }
+    assert!(!set.contains(63 << 58 | 1 << 56));
+    assert!(!CellSet::new().contains(cell));
+}
+
+#[test]
+fn test_invalid_cells_are_rejected() {
+    // Quintant 63 does not exist
+    let invalid = 63 << 58 | 1 << 56;
+    assert!(CellSet::from_cells(&[invalid]).is_err());
+    assert!(CellSet::try_from(vec![WORLD_CELL]).is_ok());
+}
@@ -0,0 +1,20 @@
+// A5
+// SPDX-License-Identifier: Apache-2.0
+// Copyright (c) A5 contributors
+
+//! Helpers shared by the integration tests
+
+use a5::LonLat;
+
+/// Signed area of a ring in the lon/lat plane, positive for CCW rings.
+/// Open and closed rings give the same result.
+pub fn planar_area(ring: &[LonLat]) -> f64 {
+    let n = ring.len();
+    (0..n)
+        .map(|i| {
+            let (a, b) = (ring[i], ring[(i + 1) % n]);
+            a.longitude() * b.latitude() - b.longitude() * a.latitude()
+        })
+        .sum::<f64>()
+        / 2.0
+}
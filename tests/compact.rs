@@ -2,10 +2,11 @@
 // SPDX-License-Identifier: Apache-2.0
 // Copyright (c) A5 contributors
 
-use a5::core::compact::{compact, uncompact};
+use a5::core::compact::{compact, compact_union, uncompact, uncompact_iter};
 use a5::core::hex::hex_to_u64;
-use a5::core::serialization::deserialize;
+use a5::core::serialization::{cell_to_children, deserialize, WORLD_CELL};
 use serde::Deserialize;
+use std::collections::HashSet;
 use std::fs;
 
 #[derive(Deserialize)]
@@ -225,3 +226,76 @@ fn test_roundtrip_all_fixtures() {
         }
     }
 }
+
+#[test]
+fn test_compact_union_overlapping_sets() {
+    let parent = cell_to_children(WORLD_CELL, Some(4)).unwrap()[100];
+    let children = cell_to_children(parent, Some(6)).unwrap();
+    let other = cell_to_children(WORLD_CELL, Some(4)).unwrap()[200];
+
+    // Descendants of a cell already in the set are absorbed by it
+    let mut cells = vec![children[3], other, children[7], parent];
+    assert_eq!(
+        compact_union(&cells).unwrap(),
+        compact(&[parent, other]).unwrap()
+    );
+
+    // Disjoint mixed-resolution pieces still compact to their parent
+    let siblings = cell_to_children(parent, None).unwrap();
+    cells = cell_to_children(siblings[0], Some(6)).unwrap();
+    cells.extend(&siblings[1..]);
+    assert_eq!(compact_union(&cells).unwrap(), vec![parent]);
+
+    // The world cell covers everything
+    assert_eq!(
+        compact_union(&[parent, WORLD_CELL]).unwrap(),
+        vec![WORLD_CELL]
+    );
+}
+
+#[test]
+fn test_uncompact_iter_matches_uncompact() {
+    let res2 = cell_to_children(WORLD_CELL, Some(2)).unwrap();
+    let res4 = cell_to_children(res2[17], Some(4)).unwrap();
+    let cells = vec![res4[3], res2[100], res4[9], res2[5]];
+
+    for target in [4, 5, 6] {
+        let expected = uncompact(&cells, target).unwrap();
+        let iter = uncompact_iter(&cells, target).unwrap();
+        assert_eq!(iter.len(), expected.len());
+        assert_eq!(iter.clone().collect::<Vec<_>>(), expected);
+        assert_eq!(
+            iter.clone().rev().collect::<Vec<_>>(),
+            expected.iter().rev().copied().collect::<Vec<_>>()
+        );
+        for n in [0, 1, 15, 16, 17, expected.len() - 1, expected.len()] {
+            assert_eq!(iter.clone().nth(n), expected.get(n).copied());
+        }
+
+        // Pages taken from both ends meet exactly
+        let mut iter = iter;
+        let mut front = Vec::new();
+        let mut back = Vec::new();
+        loop {
+            match (iter.nth(2), iter.nth_back(4)) {
+                (None, None) => break,
+                (a, b) => {
+                    front.extend(a);
+                    back.extend(b);
+                }
+            }
+        }
+        let taken: HashSet<u64> = front.iter().chain(&back).copied().collect();
+        assert_eq!(taken.len(), front.len() + back.len());
+        assert!(front.windows(2).all(|w| {
+            let (i, j) = (
+                expected.iter().position(|&c| c == w[0]).unwrap(),
+                expected.iter().position(|&c| c == w[1]).unwrap(),
+            );
+            j == i + 3
+        }));
+    }
+
+    assert_eq!(uncompact_iter(&[], 5).unwrap().next(), None);
+    assert!(uncompact_iter(&cells, 3).is_err());
+}
@@ -3,6 +3,7 @@
 // Copyright (c) A5 contributors
 
 use a5::coordinate_systems::Cartesian;
+use a5::core::error::A5Error;
 use a5::projections::crs::CRS;
 use a5::utils::vector::vec3_length;
 use approx::assert_relative_eq;
@@ -80,7 +81,8 @@ fn test_crs_throws_error_for_non_existent_vertex() {
 
     let result = crs.get_vertex(non_vertex_point);
     assert!(result.is_err(), "Should fail to find non-existent vertex");
-    assert!(result.unwrap_err().contains("Failed to find vertex in CRS"));
+    assert!(matches!(result.unwrap_err(), A5Error::Projection(message)
+        if message.contains("Failed to find vertex in CRS")));
 }
 
 #[test]
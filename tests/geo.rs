@@ -0,0 +1,108 @@
+// A5
+// SPDX-License-Identifier: Apache-2.0
+// Copyright (c) A5 contributors
+
+#![cfg(feature = "geo")]
+
+use a5::{
+    cell_to_boundary, cell_to_multipolygon, cell_to_polygon, geo_line_string_to_cells,
+    geo_multipolygon_to_cells, geo_polygon_to_cells, line_string_to_cells, lonlat_to_cell,
+    polygon_to_cells, uncompact, CellToBoundaryOptions, LonLat,
+};
+use geo_types::{coord, line_string, point, polygon, Coord, MultiPolygon, Point};
+
+#[test]
+fn test_coord_and_point_conversions() {
+    let lonlat = LonLat::new(-73.9857, 40.7484);
+    let coord: Coord<f64> = lonlat.into();
+    assert_eq!(coord, coord! { x: -73.9857, y: 40.7484 });
+    assert_eq!(LonLat::from(coord), lonlat);
+
+    let point: Point<f64> = lonlat.into();
+    assert_eq!(point, point! { x: -73.9857, y: 40.7484 });
+    assert_eq!(LonLat::from(point), lonlat);
+}
+
+#[test]
+fn test_cell_to_polygon() {
+    let cell = lonlat_to_cell(LonLat::new(10.0, 45.0), 6).unwrap();
+    let polygon = cell_to_polygon(cell, None).unwrap();
+    let boundary = cell_to_boundary(cell, None).unwrap();
+    let expected: Vec<Coord<f64>> = boundary.into_iter().map(Coord::from).collect();
+    assert_eq!(polygon.exterior().0, expected);
+    assert!(polygon.interiors().is_empty());
+
+    // An open ring from the options is closed by geo-types
+    let options = CellToBoundaryOptions::default()
+        .with_closed_ring(false)
+        .with_segments(1);
+    let polygon = cell_to_polygon(cell, Some(options)).unwrap();
+    assert_eq!(polygon.exterior().0.len(), 6);
+    assert!(polygon.exterior().is_closed());
+}
+
+#[test]
+fn test_cell_to_multipolygon() {
+    let cell = lonlat_to_cell(LonLat::new(10.0, 45.0), 6).unwrap();
+    let multipolygon = cell_to_multipolygon(cell, None).unwrap();
+    assert_eq!(multipolygon.0, vec![cell_to_polygon(cell, None).unwrap()]);
+
+    let cell = lonlat_to_cell(LonLat::new(-179.99, -10.8), 6).unwrap();
+    let multipolygon = cell_to_multipolygon(cell, None).unwrap();
+    assert_eq!(multipolygon.0.len(), 2);
+    for polygon in &multipolygon {
+        assert!(polygon.exterior().is_closed());
+        assert!(polygon.exterior().coords().all(|c| c.x.abs() <= 180.0));
+    }
+}
+
+#[test]
+fn test_geo_polygon_to_cells() {
+    let polygon = polygon!(
+        exterior: [
+            (x: 10.0, y: 45.0),
+            (x: 12.0, y: 45.0),
+            (x: 12.0, y: 47.0),
+            (x: 10.0, y: 47.0),
+        ],
+        interiors: [[
+            (x: 10.5, y: 45.5),
+            (x: 11.5, y: 45.5),
+            (x: 11.5, y: 46.5),
+            (x: 10.5, y: 46.5),
+        ]],
+    );
+    let rings: Vec<Vec<LonLat>> = std::iter::once(polygon.exterior())
+        .chain(polygon.interiors())
+        .map(|ring| ring.0.iter().map(|&c| c.into()).collect())
+        .collect();
+    assert_eq!(
+        geo_polygon_to_cells(&polygon, 7).unwrap(),
+        polygon_to_cells(&rings, 7).unwrap()
+    );
+
+    // A multipolygon merges its parts
+    let other = polygon![
+        (x: 20.0, y: 0.0),
+        (x: 21.0, y: 0.0),
+        (x: 21.0, y: 1.0),
+    ];
+    let multipolygon = MultiPolygon(vec![polygon.clone(), other.clone()]);
+    let mut expected = uncompact(&geo_polygon_to_cells(&polygon, 7).unwrap(), 7).unwrap();
+    expected.extend(uncompact(&geo_polygon_to_cells(&other, 7).unwrap(), 7).unwrap());
+    expected.sort_unstable();
+    assert_eq!(
+        uncompact(&geo_multipolygon_to_cells(&multipolygon, 7).unwrap(), 7).unwrap(),
+        expected
+    );
+}
+
+#[test]
+fn test_geo_line_string_to_cells() {
+    let line = line_string![(x: 10.0, y: 45.0), (x: 11.0, y: 45.5), (x: 12.0, y: 45.0)];
+    let waypoints: Vec<LonLat> = line.0.iter().map(|&c| c.into()).collect();
+    assert_eq!(
+        geo_line_string_to_cells(&line, 9).unwrap(),
+        line_string_to_cells(&waypoints, 9).unwrap()
+    );
+}
@@ -0,0 +1,261 @@
+// A5
+// SPDX-License-Identifier: Apache-2.0
+// Copyright (c) A5 contributors
+
+#![cfg(feature = "geojson")]
+
+use a5::{
+    cell_to_boundary, cell_to_geojson, cells_to_geojson, geojson_to_cells, grid_disk,
+    line_string_to_cells, lonlat_to_cell, polygon_to_cells, u64_to_hex, uncompact, A5Error,
+    GeoJsonOptions, LonLat,
+};
+use serde_json::{json, Value};
+
+fn ring_lengths(feature: &Value) -> Vec<Vec<usize>> {
+    feature["geometry"]["coordinates"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|polygon| {
+            polygon
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|ring| ring.as_array().unwrap().len())
+                .collect()
+        })
+        .collect()
+}
+
+fn assert_closed(ring: &Value) {
+    let ring = ring.as_array().unwrap();
+    assert_eq!(ring.first(), ring.last());
+}
+
+#[test]
+fn test_cell_to_geojson() {
+    let cell = lonlat_to_cell(LonLat::new(-73.9857, 40.7484), 8).unwrap();
+    let feature = cell_to_geojson(cell, None).unwrap();
+
+    assert_eq!(feature["type"], "Feature");
+    assert_eq!(feature["geometry"]["type"], "Polygon");
+    assert_eq!(feature["properties"]["cellIdHex"], u64_to_hex(cell));
+    assert_eq!(feature["properties"]["resolution"], 8);
+
+    let ring = &feature["geometry"]["coordinates"][0];
+    assert_closed(ring);
+    let boundary = cell_to_boundary(cell, None).unwrap();
+    assert_eq!(ring.as_array().unwrap().len(), boundary.len());
+    assert_eq!(ring[1][0], boundary[1].longitude());
+    assert_eq!(ring[1][1], boundary[1].latitude());
+}
+
+#[test]
+fn test_cell_to_geojson_segments() {
+    let cell = lonlat_to_cell(LonLat::new(10.0, 45.0), 3).unwrap();
+    let options = GeoJsonOptions {
+        segments: Some(1),
+        ..Default::default()
+    };
+    let feature = cell_to_geojson(cell, Some(options)).unwrap();
+    assert_eq!(
+        feature["geometry"]["coordinates"][0]
+            .as_array()
+            .unwrap()
+            .len(),
+        6
+    );
+}
+
+#[test]
+fn test_cell_to_geojson_split_antimeridian() {
+    let options = Some(GeoJsonOptions {
+        split_antimeridian: true,
+        ..Default::default()
+    });
+
+    // Cells away from the antimeridian stay single polygons
+    let cell = lonlat_to_cell(LonLat::new(10.0, 45.0), 5).unwrap();
+    assert_eq!(
+        cell_to_geojson(cell, options.clone()).unwrap(),
+        cell_to_geojson(cell, None).unwrap()
+    );
+
+    let cell = lonlat_to_cell(LonLat::new(179.99, 10.8), 5).unwrap();
+    let feature = cell_to_geojson(cell, options).unwrap();
+    assert_eq!(feature["geometry"]["type"], "MultiPolygon");
+    let polygons = feature["geometry"]["coordinates"].as_array().unwrap();
+    assert_eq!(polygons.len(), 2);
+    for polygon in polygons {
+        assert_closed(&polygon[0]);
+        for position in polygon[0].as_array().unwrap() {
+            assert!(position[0].as_f64().unwrap().abs() <= 180.0);
+        }
+    }
+}
+
+#[test]
+fn test_cells_to_geojson_feature_per_cell() {
+    let center = lonlat_to_cell(LonLat::new(10.0, 45.0), 6).unwrap();
+    let cells = uncompact(&grid_disk(center, 1).unwrap(), 6).unwrap();
+    let collection = cells_to_geojson(&cells, None).unwrap();
+
+    assert_eq!(collection["type"], "FeatureCollection");
+    let features = collection["features"].as_array().unwrap();
+    assert_eq!(features.len(), cells.len());
+    for (feature, cell) in features.iter().zip(&cells) {
+        assert_eq!(feature["properties"]["cellIdHex"], u64_to_hex(*cell));
+    }
+}
+
+#[test]
+fn test_cells_to_geojson_dissolve() {
+    let center = lonlat_to_cell(LonLat::new(10.0, 45.0), 6).unwrap();
+    let cells = uncompact(&grid_disk(center, 3).unwrap(), 6).unwrap();
+    let options = GeoJsonOptions {
+        segments: Some(1),
+        dissolve: true,
+        ..Default::default()
+    };
+
+    // A disk dissolves to one polygon without holes
+    let collection = cells_to_geojson(&cells, Some(options.clone())).unwrap();
+    let features = collection["features"].as_array().unwrap();
+    assert_eq!(features.len(), 1);
+    assert_eq!(features[0]["geometry"]["type"], "MultiPolygon");
+    let rings = ring_lengths(&features[0]);
+    assert_eq!(rings.len(), 1);
+    assert_eq!(rings[0].len(), 1);
+    assert_closed(&features[0]["geometry"]["coordinates"][0][0]);
+
+    // Removing the center cell leaves a pentagonal hole
+    let annulus: Vec<u64> = cells.iter().copied().filter(|&c| c != center).collect();
+    let collection = cells_to_geojson(&annulus, Some(options.clone())).unwrap();
+    let rings = ring_lengths(&collection["features"][0]);
+    assert_eq!(rings.len(), 1);
+    assert_eq!(rings[0].len(), 2);
+    assert_eq!(rings[0][1], 6);
+
+    // Disjoint cells give separate polygons
+    let far = lonlat_to_cell(LonLat::new(-100.0, -30.0), 6).unwrap();
+    let collection = cells_to_geojson(&[center, far], Some(options)).unwrap();
+    assert_eq!(
+        ring_lengths(&collection["features"][0]),
+        vec![vec![6], vec![6]]
+    );
+}
+
+#[test]
+fn test_cells_to_geojson_dissolve_compacted() {
+    // Compacted and uncompacted inputs trace the same outline
+    let center = lonlat_to_cell(LonLat::new(10.0, 45.0), 5).unwrap();
+    let compacted = grid_disk(center, 4).unwrap();
+    let cells = uncompact(&compacted, 5).unwrap();
+    let options = Some(GeoJsonOptions {
+        segments: Some(1),
+        dissolve: true,
+        ..Default::default()
+    });
+    assert_eq!(
+        cells_to_geojson(&compacted, options.clone()).unwrap(),
+        cells_to_geojson(&cells, options).unwrap()
+    );
+}
+
+#[test]
+fn test_cells_to_geojson_empty() {
+    let collection = cells_to_geojson(&[], None).unwrap();
+    assert_eq!(collection["features"].as_array().unwrap().len(), 0);
+}
+
+fn square(lon: f64, lat: f64, size: f64) -> Vec<LonLat> {
+    vec![
+        LonLat::new(lon, lat),
+        LonLat::new(lon + size, lat),
+        LonLat::new(lon + size, lat + size),
+        LonLat::new(lon, lat + size),
+        LonLat::new(lon, lat),
+    ]
+}
+
+fn to_coordinates(ring: &[LonLat]) -> Value {
+    json!(ring
+        .iter()
+        .map(|p| [p.longitude(), p.latitude()])
+        .collect::<Vec<_>>())
+}
+
+#[test]
+fn test_geojson_to_cells_point() {
+    let geometry = json!({ "type": "Point", "coordinates": [10.0, 45.0, 120.0] });
+    assert_eq!(
+        geojson_to_cells(&geometry, 9).unwrap(),
+        vec![lonlat_to_cell(LonLat::new(10.0, 45.0), 9).unwrap()]
+    );
+}
+
+#[test]
+fn test_geojson_to_cells_matches_region_functions() {
+    let ring = square(10.0, 45.0, 1.0);
+    let polygon = json!({ "type": "Polygon", "coordinates": [to_coordinates(&ring)] });
+    assert_eq!(
+        geojson_to_cells(&polygon, 7).unwrap(),
+        polygon_to_cells(std::slice::from_ref(&ring), 7).unwrap()
+    );
+
+    let line = json!({ "type": "LineString", "coordinates": to_coordinates(&ring) });
+    let mut expected = line_string_to_cells(&ring, 7).unwrap();
+    expected.sort_unstable();
+    expected.dedup();
+    assert_eq!(
+        uncompact(&geojson_to_cells(&line, 7).unwrap(), 7).unwrap(),
+        expected
+    );
+}
+
+#[test]
+fn test_geojson_to_cells_multi_geometries_merge() {
+    let a = square(10.0, 45.0, 1.0);
+    let b = square(10.5, 45.5, 1.0);
+    let multi = json!({
+        "type": "MultiPolygon",
+        "coordinates": [[to_coordinates(&a)], [to_coordinates(&b)]]
+    });
+    let cells = geojson_to_cells(&multi, 6).unwrap();
+
+    // Overlapping parts are merged, not duplicated
+    let mut expected = uncompact(&polygon_to_cells(&[a], 6).unwrap(), 6).unwrap();
+    expected.extend(uncompact(&polygon_to_cells(&[b], 6).unwrap(), 6).unwrap());
+    expected.sort_unstable();
+    expected.dedup();
+    assert_eq!(uncompact(&cells, 6).unwrap(), expected);
+
+    // A collection gives the same result as its members combined
+    let point = json!({ "type": "MultiPoint", "coordinates": [[-100.0, -30.0], [10.2, 45.2]] });
+    let collection = json!({ "type": "GeometryCollection", "geometries": [multi, point] });
+    let far = lonlat_to_cell(LonLat::new(-100.0, -30.0), 6).unwrap();
+    let mut with_point = expected.clone();
+    with_point.push(far);
+    with_point.sort_unstable();
+    assert_eq!(
+        uncompact(&geojson_to_cells(&collection, 6).unwrap(), 6).unwrap(),
+        with_point
+    );
+}
+
+#[test]
+fn test_geojson_to_cells_invalid() {
+    for geometry in [
+        json!({ "coordinates": [0.0, 0.0] }),
+        json!({ "type": "Feature", "coordinates": [0.0, 0.0] }),
+        json!({ "type": "Point" }),
+        json!({ "type": "Point", "coordinates": [0.0] }),
+        json!({ "type": "LineString", "coordinates": [[0.0, 0.0], "a"] }),
+        json!({ "type": "GeometryCollection" }),
+    ] {
+        assert!(matches!(
+            geojson_to_cells(&geometry, 5),
+            Err(A5Error::InvalidGeometry(_))
+        ));
+    }
+}
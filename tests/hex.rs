@@ -1,3 +1,4 @@
+use a5::core::error::A5Error;
 use a5::core::hex::{hex_to_u64, u64_to_hex};
 
 #[test]
@@ -23,3 +24,12 @@ fn test_hex_conversion_with_large_number() {
     let result = u64_to_hex(u64_val);
     assert_eq!(result, hex);
 }
+
+#[test]
+fn test_hex_to_u64_invalid() {
+    assert_eq!(
+        hex_to_u64("not-hex"),
+        Err(A5Error::InvalidHex("not-hex".to_string()))
+    );
+    assert!(matches!(hex_to_u64(""), Err(A5Error::InvalidHex(_))));
+}
@@ -0,0 +1,175 @@
+// A5
+// SPDX-License-Identifier: Apache-2.0
+// Copyright (c) A5 contributors
+
+use a5::core::serialization::{
+    cell_to_children, cell_to_parent, get_res0_cells, get_resolution, WORLD_CELL,
+};
+use a5::{cell_to_leaf_range, cells_to_ranges, lonlat_to_cell, A5Error, LonLat};
+
+/// A cell in `quintant` at `resolution` and its leaves
+fn cell_and_leaves(quintant: u64, resolution: i32) -> (u64, Vec<u64>) {
+    let res29 = quintant << 58 | 0x0123_4567_89ab << 2 | 0b10;
+    let cell = cell_to_parent(res29, Some(resolution)).unwrap();
+    // Quintants 42 and above fall back to resolution 29
+    let leaf_resolution = if quintant >= 42 { 29 } else { 30 };
+    (cell, cell_to_children(cell, Some(leaf_resolution)).unwrap())
+}
+
+#[test]
+fn test_leaf_range_holds_exactly_the_leaves() {
+    for quintant in [0, 7, 31, 32, 39, 40, 41, 42, 59] {
+        let (cell, leaves) = cell_and_leaves(quintant, 26);
+        let range = cell_to_leaf_range(cell).unwrap();
+        assert_eq!((range.min, range.max), (leaves[0], *leaves.last().unwrap()));
+        assert!(leaves.iter().all(|&leaf| range.contains(leaf)));
+
+        // Leaves are evenly spaced, so nothing else passes the marker filter
+        let step = range.mask + 1;
+        assert_eq!((range.max - range.min) / step + 1, leaves.len() as u64);
+        for (i, &leaf) in leaves.iter().enumerate() {
+            assert_eq!(leaf, range.min + i as u64 * step);
+        }
+    }
+}
+
+#[test]
+fn test_range_scan_matches_only_descendants() {
+    // Every id between the bounds that passes the mask is a leaf of the
+    // cell, including where other encodings interleave with the range
+    for quintant in [5, 31, 33, 41, 50] {
+        let (cell, leaves) = cell_and_leaves(quintant, 28);
+        let leaf_resolution = get_resolution(leaves[0]);
+        let range = cell_to_leaf_range(cell).unwrap();
+        let mut matched = 0;
+        for key in range.min..=range.max {
+            if range.contains(key) {
+                assert_eq!(get_resolution(key), leaf_resolution, "{:x}", key);
+                assert_eq!(cell_to_parent(key, Some(28)).unwrap(), cell, "{:x}", key);
+                matched += 1;
+            }
+        }
+        assert_eq!(matched, leaves.len());
+        assert!(!range.contains(range.min - 1) && !range.contains(range.max + 1));
+    }
+}
+
+#[test]
+fn test_leaves_fall_in_their_ancestor_ranges() {
+    for lon in (-180..180).step_by(15) {
+        for lat in (-85..=85).step_by(17) {
+            let point = LonLat::new(lon as f64 + 0.3, lat as f64 + 0.1);
+            let leaf = lonlat_to_cell(point, 30).unwrap();
+            for resolution in [1, 5, 12, 28] {
+                let cell = cell_to_parent(leaf, Some(resolution)).unwrap();
+                assert!(cell_to_leaf_range(cell).unwrap().contains(leaf));
+            }
+        }
+    }
+}
+
+#[test]
+fn test_cells_to_ranges_merges_along_the_curve() {
+    let parent = cell_to_children(WORLD_CELL, Some(4)).unwrap()[1000];
+    let children = cell_to_children(parent, Some(6)).unwrap();
+    let parent_range = cell_to_leaf_range(parent).unwrap();
+
+    // Consecutive children, in any order and with duplicates, form one range
+    let mut cells = children.clone();
+    cells.reverse();
+    cells.push(children[3]);
+    assert_eq!(cells_to_ranges(&cells).unwrap(), vec![parent_range]);
+
+    // A gap splits the range
+    let mut cells = children.clone();
+    cells.remove(5);
+    let ranges = cells_to_ranges(&cells).unwrap();
+    assert_eq!(ranges.len(), 2);
+    assert_eq!(ranges[0].min, parent_range.min);
+    assert_eq!(ranges[1].max, parent_range.max);
+    let gap = cell_to_leaf_range(children[5]).unwrap();
+    assert_eq!(ranges[0].max, gap.min - 2);
+    assert_eq!(ranges[1].min, gap.max + 2);
+
+    // Contained cells are absorbed
+    assert_eq!(
+        cells_to_ranges(&[children[9], parent]).unwrap(),
+        vec![parent_range]
+    );
+    assert!(cells_to_ranges(&[]).unwrap().is_empty());
+}
+
+#[test]
+fn test_ranges_split_between_encodings() {
+    // Origin 6 covers quintants 30-34, which use two encodings
+    let origin = get_res0_cells().unwrap()[6];
+    assert_eq!(
+        cell_to_leaf_range(origin),
+        Err(A5Error::SplitLeafRange(origin))
+    );
+    let mut quintants = cell_to_children(origin, None).unwrap();
+    quintants.sort_unstable();
+    let ranges = cells_to_ranges(&[origin]).unwrap();
+    let bounds: Vec<(u64, u64)> = ranges.iter().map(|r| (r.min, r.max)).collect();
+    assert_eq!(
+        bounds,
+        vec![
+            (
+                cell_to_leaf_range(quintants[0]).unwrap().min,
+                cell_to_leaf_range(quintants[1]).unwrap().max
+            ),
+            (
+                cell_to_leaf_range(quintants[2]).unwrap().min,
+                cell_to_leaf_range(quintants[4]).unwrap().max
+            ),
+        ]
+    );
+    assert_ne!(ranges[0].mask, ranges[1].mask);
+
+    // Every leaf of the globe falls in exactly one of the world's ranges
+    let ranges = cells_to_ranges(&[WORLD_CELL]).unwrap();
+    assert_eq!(ranges.len(), 4);
+    for quintant in [0, 31, 32, 39, 40, 41, 42, 59] {
+        for leaf in cell_and_leaves(quintant, 26).1 {
+            assert_eq!(ranges.iter().filter(|r| r.contains(leaf)).count(), 1);
+        }
+    }
+    assert_eq!(cells_to_ranges(&get_res0_cells().unwrap()).unwrap(), ranges);
+}
+
+#[test]
+fn test_origins_within_one_encoding_have_one_range() {
+    let mut split = Vec::new();
+    for (i, origin) in get_res0_cells().unwrap().into_iter().enumerate() {
+        let ranges = cells_to_ranges(&[origin]).unwrap();
+        match cell_to_leaf_range(origin) {
+            Ok(range) => assert_eq!(ranges, vec![range]),
+            Err(error) => {
+                assert_eq!(error, A5Error::SplitLeafRange(origin));
+                assert_eq!(ranges.len(), 2);
+                split.push(i);
+            }
+        }
+    }
+    // Quintants 30-34 cross 32, and 40-44 cross 42
+    assert_eq!(split, vec![6, 8]);
+
+    // Leaves of a single-range origin fall in its range
+    let origin = get_res0_cells().unwrap()[3];
+    let range = cell_to_leaf_range(origin).unwrap();
+    for quintant in cell_to_children(origin, None).unwrap() {
+        let quintant_range = cell_to_leaf_range(quintant).unwrap();
+        assert!(range.contains(quintant_range.min) && range.contains(quintant_range.max));
+    }
+}
+
+#[test]
+fn test_invalid_cells_are_rejected() {
+    let invalid = 63 << 58 | 1 << 56;
+    assert!(cell_to_leaf_range(invalid).is_err());
+    assert!(cells_to_ranges(&[invalid]).is_err());
+    assert_eq!(
+        cell_to_leaf_range(WORLD_CELL),
+        Err(A5Error::SplitLeafRange(WORLD_CELL))
+    );
+}
@@ -0,0 +1,125 @@
+// A5
+// SPDX-License-Identifier: Apache-2.0
+// Copyright (c) A5 contributors
+
+use std::collections::HashMap;
+
+use a5::core::serialization::{cell_to_children, cell_to_parent, WORLD_CELL};
+use a5::{
+    cell_to_ordinal, get_num_cells, lonlat_to_cell, ordinal_to_cell, A5Error, CellGrid, LonLat,
+};
+
+#[test]
+fn test_ordinals_number_a_level_in_curve_order() {
+    for resolution in 0..=4 {
+        let mut cells = cell_to_children(WORLD_CELL, Some(resolution)).unwrap();
+        if resolution > 0 {
+            cells.sort_unstable();
+        }
+        assert_eq!(cells.len() as u64, get_num_cells(resolution));
+        for (ordinal, &cell) in cells.iter().enumerate() {
+            assert_eq!(cell_to_ordinal(cell).unwrap(), ordinal as u64);
+            assert_eq!(ordinal_to_cell(ordinal as u64, resolution).unwrap(), cell);
+        }
+    }
+    assert_eq!(cell_to_ordinal(WORLD_CELL).unwrap(), 0);
+    assert_eq!(ordinal_to_cell(0, -1).unwrap(), WORLD_CELL);
+}
+
+#[test]
+fn test_ordinals_round_trip_at_fine_resolutions() {
+    let point = LonLat::new(-3.7, 40.4);
+    let leaf = lonlat_to_cell(point, 30).unwrap();
+    for resolution in [10, 20, 28, 29, 30] {
+        let cell = cell_to_parent(leaf, Some(resolution)).unwrap();
+        let ordinal = cell_to_ordinal(cell).unwrap();
+        assert_eq!(ordinal_to_cell(ordinal, resolution).unwrap(), cell);
+
+        // Children of a cell are consecutive ordinals
+        if resolution < 30 {
+            let children = cell_to_children(cell, None).unwrap();
+            for (i, &child) in children.iter().enumerate() {
+                assert_eq!(cell_to_ordinal(child).unwrap(), 4 * ordinal + i as u64);
+            }
+        }
+    }
+
+    // The last cell of each level
+    assert_eq!(
+        cell_to_ordinal(ordinal_to_cell((60 << 54) - 1, 28).unwrap()).unwrap(),
+        (60 << 54) - 1
+    );
+}
+
+#[test]
+fn test_ordinal_errors() {
+    assert!(matches!(
+        ordinal_to_cell(12, 0),
+        Err(A5Error::InvalidOrdinal {
+            ordinal: 12,
+            resolution: 0
+        })
+    ));
+    assert!(ordinal_to_cell(u64::MAX, 5).is_err());
+    assert!(ordinal_to_cell(0, 31).is_err());
+    // Quintants 42 and above have no resolution-30 cells
+    assert!(ordinal_to_cell(41 << 58, 30).is_ok());
+    assert!(ordinal_to_cell(42 << 58, 30).is_err());
+    assert!(cell_to_ordinal(63 << 58 | 1 << 56).is_err());
+}
+
+#[test]
+fn test_cell_grid_matches_hash_map() {
+    let resolution = 3;
+    let mut grid = CellGrid::new(resolution, 0.0f32).unwrap();
+    assert_eq!(grid.len() as u64, get_num_cells(resolution));
+
+    let mut map = HashMap::new();
+    let cells = cell_to_children(WORLD_CELL, Some(resolution)).unwrap();
+    for (i, &cell) in cells.iter().enumerate().step_by(7) {
+        let value = i as f32 * 0.5;
+        assert_eq!(grid.set(cell, value).unwrap(), 0.0);
+        map.insert(cell, value);
+    }
+    for &cell in &cells {
+        assert_eq!(
+            grid.get(cell).copied(),
+            Some(*map.get(&cell).unwrap_or(&0.0))
+        );
+    }
+    *grid.get_mut(cells[1]).unwrap() += 1.0;
+    assert_eq!(grid.get(cells[1]), Some(&1.0));
+
+    // Iteration visits every cell once, in ascending id order
+    let visited: Vec<u64> = grid.iter().map(|(cell, _)| cell).collect();
+    let mut sorted = cells.clone();
+    sorted.sort_unstable();
+    assert_eq!(visited, sorted);
+    assert!(grid
+        .iter()
+        .all(|(cell, &value)| grid.get(cell) == Some(&value)));
+    assert_eq!(grid.iter().next_back().unwrap().0, *sorted.last().unwrap());
+
+    for (_, value) in grid.iter_mut() {
+        *value = 2.0;
+    }
+    assert!(grid.values().iter().all(|&value| value == 2.0));
+}
+
+#[test]
+fn test_cell_grid_from_fn_and_errors() {
+    let grid = CellGrid::from_fn(2, |cell| cell).unwrap();
+    assert!(grid.iter().all(|(cell, &value)| cell == value));
+    assert_eq!(grid.into_values().len(), 240);
+
+    let mut grid = CellGrid::new(0, 'x').unwrap();
+    let other = cell_to_children(WORLD_CELL, Some(1)).unwrap()[0];
+    assert_eq!(grid.get(other), None);
+    assert!(matches!(
+        grid.set(other, 'y'),
+        Err(A5Error::InvalidResolution(1))
+    ));
+    assert!(grid.set(63 << 58 | 1 << 56, 'y').is_err());
+    assert!(CellGrid::new(30, 0u8).is_err());
+    assert!(CellGrid::new(-2, 0u8).is_err());
+}
@@ -0,0 +1,114 @@
+// A5
+// SPDX-License-Identifier: Apache-2.0
+// Copyright (c) A5 contributors
+
+use a5::traversal::global_neighbors::get_global_cell_neighbors;
+use a5::{
+    cell_to_parent, cells_to_polygons, compact, get_res0_cells, get_resolution, grid_disk,
+    lonlat_to_cell, multipolygon_to_cells, polygon_to_cells, uncompact, uncompact_iter, LonLat,
+    WORLD_CELL,
+};
+
+mod common;
+use common::planar_area;
+
+fn square(lon: f64, lat: f64, size: f64) -> Vec<LonLat> {
+    vec![
+        LonLat::new(lon, lat),
+        LonLat::new(lon + size, lat),
+        LonLat::new(lon + size, lat + size),
+        LonLat::new(lon, lat + size),
+    ]
+}
+
+#[test]
+fn test_round_trip_polygon_to_cells() {
+    let resolution = 7;
+    let shapes = [
+        vec![square(10.0, 40.0, 3.0)],
+        vec![square(-75.0, 38.0, 4.0), square(-74.0, 39.0, 1.5)],
+    ];
+    for shape in shapes {
+        let cells = polygon_to_cells(&shape, resolution).unwrap();
+        assert!(cells.iter().any(|&c| get_resolution(c) < resolution));
+
+        let polygons = cells_to_polygons(&cells, None).unwrap();
+        assert_eq!(polygons.len(), 1);
+        assert_eq!(polygons[0].len(), shape.len());
+        assert_eq!(multipolygon_to_cells(&polygons, resolution).unwrap(), cells);
+    }
+}
+
+#[test]
+fn test_rings_are_closed_and_oriented() {
+    let center = lonlat_to_cell(LonLat::new(10.0, 45.0), 6).unwrap();
+    let disk = uncompact(&grid_disk(center, 3).unwrap(), 6).unwrap();
+    let annulus: Vec<u64> = disk.iter().copied().filter(|&c| c != center).collect();
+    let far = lonlat_to_cell(LonLat::new(-100.0, -30.0), 6).unwrap();
+
+    let mut cells = annulus.clone();
+    cells.push(far);
+    let polygons = cells_to_polygons(&cells, None).unwrap();
+    assert_eq!(polygons.len(), 2);
+    let mut holes = 0;
+    for polygon in &polygons {
+        for (i, ring) in polygon.iter().enumerate() {
+            assert_eq!(ring.first(), ring.last());
+            if i == 0 {
+                assert!(planar_area(ring) > 0.0);
+            } else {
+                assert!(planar_area(ring) < 0.0);
+                holes += 1;
+            }
+        }
+    }
+    assert_eq!(holes, 1);
+
+    // Mixed resolutions trace the same outline as the uncompacted cells
+    assert_eq!(
+        cells_to_polygons(&compact(&annulus).unwrap(), None).unwrap(),
+        cells_to_polygons(&annulus, None).unwrap()
+    );
+}
+
+#[test]
+fn test_empty_world_and_invalid_cells() {
+    assert!(cells_to_polygons(&[], None).unwrap().is_empty());
+    assert!(cells_to_polygons(&[WORLD_CELL], None).unwrap().is_empty());
+    assert!(cells_to_polygons(&get_res0_cells().unwrap(), None)
+        .unwrap()
+        .is_empty());
+    assert!(cells_to_polygons(&[63 << 58 | 1 << 56], None).is_err());
+}
+
+#[test]
+fn test_wide_resolution_spread() {
+    let coarse = lonlat_to_cell(LonLat::new(10.0, 45.0), 4).unwrap();
+
+    // A distant fine cell leaves the coarse one at its own resolution
+    let far = lonlat_to_cell(LonLat::new(25.0, 45.0), 25).unwrap();
+    let polygons = cells_to_polygons(&[coarse, far], Some(1)).unwrap();
+    assert_eq!(polygons.len(), 2);
+    for polygon in &polygons {
+        assert_eq!(polygon.len(), 1);
+        assert_eq!(polygon[0].len(), 6);
+    }
+
+    // A fine cell across an edge refines the coarse one only along the
+    // outline, which is still that of the uncompacted cells
+    for resolution in [9, 13] {
+        let fine = uncompact_iter(&[coarse], resolution)
+            .unwrap()
+            .flat_map(|cell| get_global_cell_neighbors(cell, true))
+            .find(|&cell| cell_to_parent(cell, Some(4)).unwrap() != coarse)
+            .unwrap();
+        let polygons = cells_to_polygons(&[coarse, fine], None).unwrap();
+        assert_eq!(polygons.len(), 1);
+        assert_eq!(polygons[0].len(), 1);
+        if resolution == 9 {
+            let mut cells = uncompact(&[coarse], resolution).unwrap();
+            cells.push(fine);
+            assert_eq!(cells_to_polygons(&cells, None).unwrap(), polygons);
+        }
+    }
+}
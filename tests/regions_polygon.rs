@@ -1,7 +1,14 @@
 use a5::coordinate_systems::LonLat;
-use a5::core::compact::uncompact;
+use a5::core::cell::{cell_to_boundary, lonlat_to_cell, CellToBoundaryOptions};
+use a5::core::compact::{compact_union, uncompact};
+use a5::core::coordinate_transforms::{from_lon_lat, to_cartesian};
 use a5::core::hex::u64_to_hex;
-use a5::regions::polygon::polygon_to_cells;
+use a5::geometry::prepared_polygon::{point_in_prepared_polygon, prepare_polygon};
+use a5::regions::polygon::{
+    multipolygon_to_cells, multipolygon_to_cells_with_mode, polygon_to_cells,
+    polygon_to_cells_with_mode, ContainmentMode,
+};
+use a5::traversal::global_neighbors::get_global_cell_neighbors;
 use serde::Deserialize;
 use std::collections::HashSet;
 use std::fs;
@@ -136,3 +143,285 @@ fn test_polygon_to_cells_country_fixtures() {
         );
     }
 }
+
+fn mode_cells(rings: &[Vec<LonLat>], resolution: i32, mode: ContainmentMode) -> HashSet<u64> {
+    let cells = polygon_to_cells_with_mode(rings, resolution, mode).unwrap();
+    uncompact(&cells, resolution).unwrap().into_iter().collect()
+}
+
+/// How many of the cell's densified boundary points lie inside, and how many were tested
+fn boundary_points_inside(cell: u64, rings: &[Vec<LonLat>]) -> (usize, usize) {
+    boundary_points_inside_union(cell, &[rings.to_vec()])
+}
+
+/// As `boundary_points_inside`, counting points inside any of the parts
+fn boundary_points_inside_union(cell: u64, multipolygon: &[Vec<Vec<LonLat>>]) -> (usize, usize) {
+    let preps: Vec<_> = multipolygon
+        .iter()
+        .map(|rings| {
+            prepare_polygon(
+                rings
+                    .iter()
+                    .map(|ring| {
+                        ring.iter()
+                            .map(|&v| to_cartesian(from_lon_lat(v)))
+                            .collect()
+                    })
+                    .collect(),
+            )
+        })
+        .collect();
+    let options = CellToBoundaryOptions::default()
+        .with_closed_ring(false)
+        .with_segments(8);
+    let boundary = cell_to_boundary(cell, Some(options)).unwrap();
+    let inside = boundary
+        .iter()
+        .filter(|&&v| {
+            let p = to_cartesian(from_lon_lat(v));
+            preps.iter().any(|prep| point_in_prepared_polygon(p, prep))
+        })
+        .count();
+    (inside, boundary.len())
+}
+
+#[test]
+fn test_polygon_to_cells_with_mode_nesting() {
+    let rings = vec![
+        vec![
+            LonLat::new(-5.3, 54.1),
+            LonLat::new(15.2, 53.7),
+            LonLat::new(14.9, 44.2),
+            LonLat::new(-4.8, 43.9),
+        ],
+        vec![
+            LonLat::new(2.1, 51.3),
+            LonLat::new(8.2, 50.9),
+            LonLat::new(7.9, 47.2),
+            LonLat::new(2.3, 46.8),
+        ],
+    ];
+    let resolution = 6;
+    let center = mode_cells(&rings, resolution, ContainmentMode::Center);
+    let intersects = mode_cells(&rings, resolution, ContainmentMode::Intersects);
+    let contains = mode_cells(&rings, resolution, ContainmentMode::Contains);
+
+    assert_eq!(
+        polygon_to_cells_with_mode(&rings, resolution, ContainmentMode::Center).unwrap(),
+        polygon_to_cells(&rings, resolution).unwrap()
+    );
+    assert!(contains.is_subset(&center));
+    assert!(center.is_subset(&intersects));
+    assert!(contains.len() < center.len() && center.len() < intersects.len());
+
+    // Contained cells lie entirely inside
+    for &cell in &contains {
+        let (inside, total) = boundary_points_inside(cell, &rings);
+        assert_eq!(inside, total, "{} pokes out", u64_to_hex(cell));
+    }
+    // Cells dropped from the center result straddle the boundary
+    for &cell in center.difference(&contains) {
+        let (inside, total) = boundary_points_inside(cell, &rings);
+        assert!(inside < total, "{} is entirely inside", u64_to_hex(cell));
+    }
+    // Cells next to the intersecting set lie entirely outside
+    for &cell in &intersects {
+        for neighbor in get_global_cell_neighbors(cell, false) {
+            if !intersects.contains(&neighbor) {
+                let (inside, _) = boundary_points_inside(neighbor, &rings);
+                assert_eq!(inside, 0, "{} overlaps", u64_to_hex(neighbor));
+            }
+        }
+    }
+}
+
+#[test]
+fn test_polygon_to_cells_with_mode_small_polygon() {
+    // A polygon far smaller than a cell only intersects the cell holding it
+    let ring = vec![
+        LonLat::new(10.0, 45.0),
+        LonLat::new(10.001, 45.0),
+        LonLat::new(10.001, 45.001),
+        LonLat::new(10.0, 45.001),
+    ];
+    let resolution = 5;
+    let cell = lonlat_to_cell(LonLat::new(10.0005, 45.0005), resolution).unwrap();
+    let rings = [ring];
+    assert_eq!(
+        polygon_to_cells_with_mode(&rings, resolution, ContainmentMode::Intersects).unwrap(),
+        vec![cell]
+    );
+    assert!(
+        polygon_to_cells_with_mode(&rings, resolution, ContainmentMode::Contains)
+            .unwrap()
+            .is_empty()
+    );
+}
+
+#[test]
+fn test_polygon_to_cells_with_mode_large_polygon() {
+    // Large enough for the coarse flood-fill phase to emit parent cells
+    let rings = vec![vec![
+        LonLat::new(-20.0, 30.0),
+        LonLat::new(30.0, 30.0),
+        LonLat::new(30.0, 60.0),
+        LonLat::new(-20.0, 60.0),
+    ]];
+    let resolution = 7;
+    let center = mode_cells(&rings, resolution, ContainmentMode::Center);
+    let intersects = mode_cells(&rings, resolution, ContainmentMode::Intersects);
+    let contains = mode_cells(&rings, resolution, ContainmentMode::Contains);
+    assert!(contains.is_subset(&center));
+    assert!(center.is_subset(&intersects));
+
+    // Every cell differing between the modes is cut by the boundary
+    for &cell in intersects.difference(&contains) {
+        let (inside, total) = boundary_points_inside(cell, &rings);
+        assert!(inside < total, "{} is entirely inside", u64_to_hex(cell));
+    }
+}
+
+#[test]
+fn test_multipolygon_to_cells_adjacent_parts() {
+    // A quadrilateral split along its diagonal: the parts share an edge and
+    // their union has exactly the original great-circle edges
+    let (a, b, c, d) = (
+        LonLat::new(-5.3, 54.1),
+        LonLat::new(15.2, 53.7),
+        LonLat::new(14.9, 44.2),
+        LonLat::new(-4.8, 43.9),
+    );
+    let multipolygon = vec![vec![vec![a, b, c]], vec![vec![a, c, d, a]]];
+    for resolution in [4, 6, 8] {
+        assert_eq!(
+            multipolygon_to_cells(&multipolygon, resolution).unwrap(),
+            polygon_to_cells(&[vec![a, b, c, d]], resolution).unwrap(),
+            "resolution {}",
+            resolution
+        );
+    }
+}
+
+#[test]
+fn test_multipolygon_to_cells_matches_union_of_parts() {
+    let multipolygon = vec![
+        // Outer square with a hole
+        vec![
+            vec![
+                LonLat::new(0.0, 40.0),
+                LonLat::new(10.0, 40.0),
+                LonLat::new(10.0, 50.0),
+                LonLat::new(0.0, 50.0),
+            ],
+            vec![
+                LonLat::new(3.0, 43.0),
+                LonLat::new(7.0, 43.0),
+                LonLat::new(7.0, 47.0),
+                LonLat::new(3.0, 47.0),
+            ],
+        ],
+        // Island inside the hole
+        vec![vec![
+            LonLat::new(4.0, 44.0),
+            LonLat::new(6.0, 44.0),
+            LonLat::new(6.0, 46.0),
+            LonLat::new(4.0, 46.0),
+        ]],
+        // Far away part, and a degenerate one that is ignored
+        vec![vec![
+            LonLat::new(-80.0, -10.0),
+            LonLat::new(-75.0, -10.0),
+            LonLat::new(-75.0, -5.0),
+        ]],
+        vec![vec![LonLat::new(1.0, 1.0), LonLat::new(2.0, 2.0)]],
+    ];
+    let resolution = 6;
+    for mode in [ContainmentMode::Center, ContainmentMode::Intersects] {
+        let mut parts: Vec<u64> = Vec::new();
+        for polygon in &multipolygon {
+            parts.extend(polygon_to_cells_with_mode(polygon, resolution, mode).unwrap());
+        }
+        assert_eq!(
+            multipolygon_to_cells_with_mode(&multipolygon, resolution, mode).unwrap(),
+            compact_union(&parts).unwrap(),
+            "{:?}",
+            mode
+        );
+    }
+
+    // Contained cells lie entirely inside the union, and the others of the
+    // center result reach out of it
+    let mode_cells = |mode| -> HashSet<u64> {
+        let cells = multipolygon_to_cells_with_mode(&multipolygon, resolution, mode).unwrap();
+        uncompact(&cells, resolution).unwrap().into_iter().collect()
+    };
+    let center = mode_cells(ContainmentMode::Center);
+    let contains = mode_cells(ContainmentMode::Contains);
+    assert!(contains.is_subset(&center));
+    for &cell in &center {
+        let (inside, total) = boundary_points_inside_union(cell, &multipolygon);
+        assert_eq!(
+            contains.contains(&cell),
+            inside == total,
+            "{}",
+            u64_to_hex(cell)
+        );
+    }
+    assert!(multipolygon_to_cells(&[], resolution).unwrap().is_empty());
+}
+
+#[test]
+fn test_multipolygon_to_cells_contains_across_shared_border() {
+    // Two squares sharing the meridian at 5°, and the hexagon of their union
+    let corner = |lon, lat| LonLat::new(lon, lat);
+    let west = vec![vec![
+        corner(0.0, 40.0),
+        corner(5.0, 40.0),
+        corner(5.0, 45.0),
+        corner(0.0, 45.0),
+    ]];
+    let east = vec![vec![
+        corner(5.0, 40.0),
+        corner(10.0, 40.0),
+        corner(10.0, 45.0),
+        corner(5.0, 45.0),
+    ]];
+    let union = vec![vec![
+        corner(0.0, 40.0),
+        corner(5.0, 40.0),
+        corner(10.0, 40.0),
+        corner(10.0, 45.0),
+        corner(5.0, 45.0),
+        corner(0.0, 45.0),
+    ]];
+    let multipolygon = vec![west.clone(), east.clone()];
+    for resolution in [5, 7] {
+        let contains =
+            multipolygon_to_cells_with_mode(&multipolygon, resolution, ContainmentMode::Contains)
+                .unwrap();
+        assert_eq!(
+            contains,
+            polygon_to_cells_with_mode(&union, resolution, ContainmentMode::Contains).unwrap(),
+            "resolution {}",
+            resolution
+        );
+
+        // Cells across the shared border are kept, unlike for either part
+        let mut parts =
+            polygon_to_cells_with_mode(&west, resolution, ContainmentMode::Contains).unwrap();
+        parts.extend(
+            polygon_to_cells_with_mode(&east, resolution, ContainmentMode::Contains).unwrap(),
+        );
+        let parts: HashSet<u64> = uncompact(&parts, resolution).unwrap().into_iter().collect();
+        let straddling: Vec<u64> = uncompact(&contains, resolution)
+            .unwrap()
+            .into_iter()
+            .filter(|cell| !parts.contains(cell))
+            .collect();
+        assert!(!straddling.is_empty());
+        for cell in straddling {
+            assert!(boundary_points_inside(cell, &west).0 > 0);
+            assert!(boundary_points_inside(cell, &east).0 > 0);
+        }
+    }
+}
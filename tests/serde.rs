@@ -0,0 +1,75 @@
+// A5
+// SPDX-License-Identifier: Apache-2.0
+// Copyright (c) A5 contributors
+
+#![cfg(feature = "serde")]
+
+use a5::{lonlat_to_cell, A5Cell, CellId, CellToBoundaryOptions, Degrees, LonLat, Radians};
+use serde_json::json;
+
+#[test]
+fn test_lonlat_round_trip() {
+    let lonlat = LonLat::new(-73.9857, 40.7484);
+    let value = serde_json::to_value(lonlat).unwrap();
+    assert_eq!(value, json!({ "longitude": -73.9857, "latitude": 40.7484 }));
+    assert_eq!(serde_json::from_value::<LonLat>(value).unwrap(), lonlat);
+}
+
+#[test]
+fn test_angles_serialize_as_numbers() {
+    assert_eq!(serde_json::to_value(Degrees(12.5)).unwrap(), json!(12.5));
+    assert_eq!(serde_json::to_value(Radians(0.25)).unwrap(), json!(0.25));
+    assert_eq!(
+        serde_json::from_value::<Radians>(json!(0.25)).unwrap(),
+        Radians(0.25)
+    );
+}
+
+#[test]
+fn test_a5cell_round_trip() {
+    let cell = A5Cell {
+        origin_id: 3,
+        segment: 2,
+        s: 1234,
+        resolution: 9,
+    };
+    let text = serde_json::to_string(&cell).unwrap();
+    assert_eq!(serde_json::from_str::<A5Cell>(&text).unwrap(), cell);
+}
+
+#[test]
+fn test_boundary_options_defaults() {
+    let options: CellToBoundaryOptions = serde_json::from_value(json!({ "segments": 4 })).unwrap();
+    assert!(options.closed_ring);
+    assert_eq!(options.segments, Some(4));
+    let options: CellToBoundaryOptions = serde_json::from_value(json!({})).unwrap();
+    assert_eq!(options, CellToBoundaryOptions::default());
+}
+
+#[test]
+fn test_cell_id_serializes_as_hex() {
+    let cell = lonlat_to_cell(LonLat::new(2.35, 48.85), 12).unwrap();
+    let cell_id = CellId::try_from(cell).unwrap();
+    let value = serde_json::to_value(cell_id).unwrap();
+    assert_eq!(value, json!(a5::u64_to_hex(cell)));
+    assert_eq!(serde_json::from_value::<CellId>(value).unwrap(), cell_id);
+
+    // Deserialization rejects malformed ids
+    assert!(serde_json::from_value::<CellId>(json!("not-hex")).is_err());
+    assert!(serde_json::from_value::<CellId>(json!("fe00000000000000")).is_err());
+    assert!(serde_json::from_value::<CellId>(json!(12)).is_err());
+}
+
+#[test]
+fn test_cell_set_round_trip() {
+    let parent = a5::cell_to_children(a5::WORLD_CELL, Some(3)).unwrap()[40];
+    let children = a5::cell_to_children(parent, None).unwrap();
+    let set = a5::CellSet::from_cells(&children[1..]).unwrap();
+    let value = serde_json::to_value(&set).unwrap();
+    assert_eq!(value, json!(children[1..]));
+
+    // Deserializing normalizes the cells
+    let full: a5::CellSet = serde_json::from_value(json!(children)).unwrap();
+    assert_eq!(full.cells(), &[parent]);
+    assert!(serde_json::from_value::<a5::CellSet>(json!([63u64 << 58 | 1 << 56])).is_err());
+}
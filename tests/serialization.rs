@@ -1,7 +1,9 @@
+use a5::core::error::{A5Error, InvalidCellReason};
 use a5::core::origin::get_origins;
 use a5::core::serialization::{
-    cell_to_children, cell_to_parent, deserialize, get_res0_cells, get_resolution, get_stride,
-    is_child_of, is_first_child, serialize, FIRST_HILBERT_RESOLUTION, MAX_RESOLUTION,
+    cell_children_iter, cell_to_child_pos, cell_to_children, cell_to_parent, child_pos_to_cell,
+    deserialize, get_res0_cells, get_resolution, get_stride, is_child_of, is_first_child,
+    is_valid_cell, serialize, validate_cell, FIRST_HILBERT_RESOLUTION, MAX_RESOLUTION, WORLD_CELL,
 };
 use a5::core::utils::A5Cell;
 use serde_json::Value;
@@ -106,10 +108,13 @@ fn test_throws_error_when_s_is_too_large_for_resolution() {
         resolution: 3,
     };
     let result = serialize(&cell);
-    assert!(result.is_err());
-    assert!(result
-        .unwrap_err()
-        .contains("S (16) is too large for resolution level 3"));
+    assert_eq!(
+        result.unwrap_err(),
+        A5Error::InvalidS {
+            s: 16,
+            resolution: 3
+        }
+    );
 }
 
 #[test]
@@ -124,8 +129,7 @@ fn test_throws_error_when_resolution_exceeds_maximum() {
         resolution: 31,
     };
     let result = serialize(&cell);
-    assert!(result.is_err());
-    assert!(result.unwrap_err().contains("Resolution (31) is too large"));
+    assert_eq!(result.unwrap_err(), A5Error::InvalidResolution(31));
 }
 
 // =============================================================================
@@ -626,10 +630,10 @@ fn test_res30_throws_for_s_too_large() {
         s: 1u64 << 58,
         resolution: 30,
     });
-    assert!(result.is_err());
-    assert!(result
-        .unwrap_err()
-        .contains("too large for resolution level 30"));
+    assert!(matches!(
+        result.unwrap_err(),
+        A5Error::InvalidS { resolution: 30, .. }
+    ));
 }
 
 #[test]
@@ -870,8 +874,7 @@ fn test_res30_cell_to_children_throws_at_max() {
     })
     .unwrap();
     let result = cell_to_children(cell, None);
-    assert!(result.is_err());
-    assert!(result.unwrap_err().contains("exceeds maximum resolution"));
+    assert_eq!(result.unwrap_err(), A5Error::InvalidResolution(31));
 }
 
 // =============================================================================
@@ -1022,3 +1025,272 @@ fn test_is_child_of_returns_false_when_child_is_shallower_than_parent() {
     let parent = cell_to_children(grandparent, None).unwrap()[0]; // res 5
     assert!(!is_child_of(grandparent, parent, 5));
 }
+
+// =============================================================================
+// validate_cell tests
+// =============================================================================
+
+fn invalid(cell: u64, reason: InvalidCellReason) -> Result<(), A5Error> {
+    Err(A5Error::InvalidCell { cell, reason })
+}
+
+#[test]
+fn test_validate_cell_accepts_hierarchy() {
+    assert!(is_valid_cell(WORLD_CELL));
+    for res in 0..=3 {
+        for cell in cell_to_children(WORLD_CELL, Some(res)).unwrap() {
+            assert!(is_valid_cell(cell), "{:x} (res {})", cell, res);
+        }
+    }
+
+    // Deepest cells of every origin, including res-30 cells on all three marker patterns
+    let origins = get_origins();
+    for origin in origins.iter() {
+        for segment in 0..5 {
+            for resolution in [MAX_RESOLUTION - 1, MAX_RESOLUTION] {
+                let cell = serialize(&A5Cell {
+                    origin_id: origin.id,
+                    segment,
+                    s: 0x123456789abcd,
+                    resolution,
+                })
+                .unwrap();
+                assert!(is_valid_cell(cell), "{:x}", cell);
+                assert_eq!(validate_cell(cell), Ok(()));
+            }
+        }
+    }
+}
+
+#[test]
+fn test_validate_cell_res30_marker_patterns() {
+    assert!(is_valid_cell(u64::MAX)); // ...1, quintant 31
+    assert!(is_valid_cell(0b100)); // ...100, quintant 32
+    assert!(is_valid_cell((1u64 << 63) | 0b10000)); // ...10000, quintant 41
+    assert_eq!(get_resolution((1u64 << 63) | 0b10000), MAX_RESOLUTION);
+}
+
+#[test]
+fn test_validate_cell_rejects_bad_marker() {
+    // Even bit positions above the res-30 patterns are never markers
+    for bit in (6..=54).step_by(2) {
+        let cell = (3u64 << 58) | (1u64 << bit);
+        assert_eq!(
+            validate_cell(cell),
+            invalid(cell, InvalidCellReason::ResolutionMarker),
+            "bit {}",
+            bit
+        );
+    }
+    // Lowest set bit inside the origin field
+    let cell = 1u64 << 58;
+    assert_eq!(
+        validate_cell(cell),
+        invalid(cell, InvalidCellReason::ResolutionMarker)
+    );
+}
+
+#[test]
+fn test_validate_cell_rejects_bad_origin() {
+    // res 0 with origin 12
+    let cell = (12u64 << 58) | (1u64 << 57);
+    assert_eq!(
+        validate_cell(cell),
+        invalid(cell, InvalidCellReason::Origin)
+    );
+    // res 1 and res 5 with quintant 60
+    let cell = (60u64 << 58) | (1u64 << 56);
+    assert_eq!(
+        validate_cell(cell),
+        invalid(cell, InvalidCellReason::Origin)
+    );
+    let cell = (63u64 << 58) | (1u64 << 49);
+    assert_eq!(
+        validate_cell(cell),
+        invalid(cell, InvalidCellReason::Origin)
+    );
+    assert!(deserialize(cell).is_err());
+}
+
+#[test]
+fn test_validate_cell_rejects_unused_bits() {
+    // res 1 has no Hilbert bits, so bit 57 must be clear
+    let cell = (7u64 << 58) | (1u64 << 57) | (1u64 << 56);
+    assert_eq!(get_resolution(cell), 1);
+    assert_eq!(
+        validate_cell(cell),
+        invalid(cell, InvalidCellReason::UnusedBits)
+    );
+}
+
+#[test]
+fn test_validate_cell_matches_canonical_round_trip() {
+    // A well-formed id is exactly one that survives deserialize -> serialize
+    let mut state = 0x2545f4914f6cdd1du64;
+    for i in 0..20000 {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        // Clear a varying number of low bits to reach every marker position
+        let cell = state & !((1u64 << (i % 60)) - 1);
+        let canonical = deserialize(cell)
+            .and_then(|c| serialize(&c))
+            .map(|c| c == cell)
+            .unwrap_or(false);
+        assert_eq!(is_valid_cell(cell), canonical, "{:x}", cell);
+    }
+}
+
+#[test]
+fn test_cell_children_iter_matches_cell_to_children() {
+    let res0 = get_res0_cells().unwrap()[4];
+    let res1 = cell_to_children(res0, None).unwrap()[2];
+    let res5 = cell_to_children(res1, Some(5)).unwrap()[77];
+    // Resolution 30 uses its own quintant encodings and falls back to 29 above quintant 41
+    let res29 = 35 << 58 | 0x1234 << 2 | 0b10;
+    let res29_fallback = 45 << 58 | 0x1234 << 2 | 0b10;
+    let cases = [
+        (WORLD_CELL, -1),
+        (WORLD_CELL, 0),
+        (WORLD_CELL, 1),
+        (WORLD_CELL, 3),
+        (res0, 0),
+        (res0, 1),
+        (res0, 4),
+        (res1, 2),
+        (res1, 5),
+        (res5, 9),
+        (res29, 30),
+        (res29_fallback, 30),
+    ];
+    for (cell, resolution) in cases {
+        let expected = cell_to_children(cell, Some(resolution)).unwrap();
+        let children = cell_children_iter(cell, Some(resolution)).unwrap();
+        assert_eq!(children.len(), expected.len());
+        assert_eq!(children.clone().collect::<Vec<_>>(), expected);
+        assert_eq!(
+            children.clone().rev().collect::<Vec<_>>(),
+            expected.iter().rev().copied().collect::<Vec<_>>()
+        );
+        for n in [0, 1, expected.len() / 2, expected.len() - 1, expected.len()] {
+            assert_eq!(children.clone().nth(n), expected.get(n).copied());
+            assert_eq!(
+                children.clone().nth_back(n),
+                expected.len().checked_sub(n + 1).map(|i| expected[i])
+            );
+        }
+    }
+    assert_eq!(
+        cell_children_iter(res1, None).unwrap().collect::<Vec<_>>(),
+        cell_to_children(res1, None).unwrap()
+    );
+}
+
+#[test]
+fn test_cell_children_iter_pages_deep_levels() {
+    // Far too many to materialize: 60 * 4^27 cells
+    let mut children = cell_children_iter(WORLD_CELL, Some(28)).unwrap();
+    assert_eq!(children.len() as u64, 60 << 54);
+
+    let first = children.next().unwrap();
+    let last = children.next_back().unwrap();
+    assert_eq!(get_resolution(first), 28);
+    assert_eq!(get_resolution(last), 28);
+    assert_eq!(children.len() as u64, (60 << 54) - 2);
+
+    // A page in the middle is consecutive along the curve
+    let page: Vec<u64> = children.clone().skip((1 << 40) - 1).take(8).collect();
+    let parent = cell_to_parent(page[0], Some(27)).unwrap();
+    assert_eq!(page[..4], cell_to_children(parent, None).unwrap()[..]);
+    assert!(page.windows(2).all(|w| w[1] - w[0] == get_stride(28)));
+
+    // Meeting in the middle leaves nothing
+    let mut two = cell_children_iter(page[0], Some(29)).unwrap().skip(2);
+    assert_eq!(two.len(), 2);
+    assert!(two.next().is_some() && two.next_back().is_some());
+    assert_eq!(two.next(), None);
+    assert_eq!(two.next_back(), None);
+}
+
+#[test]
+fn test_cell_children_iter_errors() {
+    let cell = cell_to_children(WORLD_CELL, Some(4)).unwrap()[10];
+    assert!(matches!(
+        cell_children_iter(cell, Some(3)),
+        Err(A5Error::InvalidTargetResolution { .. })
+    ));
+    assert!(matches!(
+        cell_children_iter(cell, Some(31)),
+        Err(A5Error::InvalidResolution(31))
+    ));
+    // Only materializing deep levels is refused
+    assert!(cell_children_iter(cell, Some(30)).is_ok());
+    assert!(matches!(
+        cell_to_children(cell, Some(30)),
+        Err(A5Error::ResolutionDifferenceTooLarge { .. })
+    ));
+}
+
+#[test]
+fn test_child_pos_round_trip() {
+    let res0 = get_res0_cells().unwrap()[9];
+    let res1 = cell_to_children(res0, None).unwrap()[4];
+    let res6 = cell_to_children(res1, Some(6)).unwrap()[300];
+    let cases = [
+        (WORLD_CELL, 0),
+        (WORLD_CELL, 1),
+        (WORLD_CELL, 2),
+        (res0, 1),
+        (res0, 3),
+        (res1, 4),
+        (res6, 9),
+    ];
+    for (parent, resolution) in cases {
+        let parent_resolution = get_resolution(parent);
+        for (position, child) in cell_to_children(parent, Some(resolution))
+            .unwrap()
+            .into_iter()
+            .enumerate()
+        {
+            assert_eq!(
+                cell_to_child_pos(child, parent_resolution).unwrap(),
+                position as u64
+            );
+            assert_eq!(
+                child_pos_to_cell(position as u64, parent, resolution).unwrap(),
+                child
+            );
+        }
+    }
+
+    // Any ancestor resolution of a deep cell, including itself. Quintants
+    // from 42 have no res-30 cells, so stay below them
+    let res6 = cell_to_children(get_res0_cells().unwrap()[2], Some(6)).unwrap()[300];
+    let deep = child_pos_to_cell(123_456_789, res6, 30).unwrap();
+    assert_eq!(get_resolution(deep), 30);
+    for parent_resolution in -1..=30 {
+        let parent = cell_to_parent(deep, Some(parent_resolution)).unwrap();
+        let position = cell_to_child_pos(deep, parent_resolution).unwrap();
+        assert_eq!(child_pos_to_cell(position, parent, 30).unwrap(), deep);
+    }
+    assert_eq!(cell_to_child_pos(deep, 6).unwrap(), 123_456_789);
+    assert_eq!(cell_to_child_pos(deep, 30).unwrap(), 0);
+}
+
+#[test]
+fn test_child_pos_errors() {
+    let cell = cell_to_children(WORLD_CELL, Some(3)).unwrap()[50];
+    assert!(matches!(
+        cell_to_child_pos(cell, 4),
+        Err(A5Error::InvalidTargetResolution { .. })
+    ));
+    assert!(matches!(
+        child_pos_to_cell(64, cell, 6),
+        Err(A5Error::InvalidChildPosition {
+            position: 64,
+            count: 64
+        })
+    ));
+    assert!(child_pos_to_cell(63, cell, 6).is_ok());
+    assert!(cell_to_child_pos(63 << 58 | 1 << 56, 0).is_err());
+}
@@ -3,6 +3,7 @@
 // Copyright (c) A5 contributors
 
 use a5::coordinate_systems::Cartesian;
+use a5::geometry::spherical_polygon::spherical_triangle_area;
 use a5::geometry::SphericalPolygonShape;
 use approx::assert_abs_diff_eq;
 use serde_json::Value;
@@ -238,3 +239,25 @@ fn test_get_area_degenerate_polygons() {
     ]);
     assert_eq!(two_vertices.get_area().get(), 0.0);
 }
+
+#[test]
+fn test_get_area_tiny_polygons() {
+    // A square 1e-8 radians across keeps its relative precision
+    let side = 1e-8;
+    let point = |lon: f64, lat: f64| {
+        Cartesian::new(lat.cos() * lon.cos(), lat.cos() * lon.sin(), lat.sin())
+    };
+    let square = vec![
+        point(0.3, 0.4),
+        point(0.3 + side, 0.4),
+        point(0.3 + side, 0.4 + side),
+        point(0.3, 0.4 + side),
+    ];
+    let expected = side * side * (0.4 + side / 2.0).cos();
+    let area = SphericalPolygonShape::new(square.clone()).get_area().get();
+    assert_abs_diff_eq!(area / expected, 1.0, epsilon = 1e-6);
+
+    // and so does each half of it
+    let half = spherical_triangle_area(square[0], square[1], square[2]).get();
+    assert_abs_diff_eq!(half / expected, 0.5, epsilon = 1e-6);
+}
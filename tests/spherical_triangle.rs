@@ -3,6 +3,7 @@
 // Copyright (c) A5 contributors
 
 use a5::coordinate_systems::Cartesian;
+use a5::core::error::A5Error;
 use a5::geometry::SphericalTriangleShape;
 use approx::assert_abs_diff_eq;
 use serde_json::Value;
@@ -31,7 +32,9 @@ fn test_constructor() {
     assert!(result.is_err());
     assert_eq!(
         result.unwrap_err(),
-        "SphericalTriangleShape requires exactly 3 vertices"
+        A5Error::DegenerateGeometry(
+            "SphericalTriangleShape requires exactly 3 vertices".to_string()
+        )
     );
 
     let result = SphericalTriangleShape::new(vec![
@@ -41,7 +44,9 @@ fn test_constructor() {
     assert!(result.is_err());
     assert_eq!(
         result.unwrap_err(),
-        "SphericalTriangleShape requires exactly 3 vertices"
+        A5Error::DegenerateGeometry(
+            "SphericalTriangleShape requires exactly 3 vertices".to_string()
+        )
     );
 
     let result = SphericalTriangleShape::new(vec![
@@ -53,7 +58,9 @@ fn test_constructor() {
     assert!(result.is_err());
     assert_eq!(
         result.unwrap_err(),
-        "SphericalTriangleShape requires exactly 3 vertices"
+        A5Error::DegenerateGeometry(
+            "SphericalTriangleShape requires exactly 3 vertices".to_string()
+        )
     );
 
     // Test accepts exactly 3 vertices
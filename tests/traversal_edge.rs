@@ -0,0 +1,146 @@
+// A5
+// SPDX-License-Identifier: Apache-2.0
+// Copyright (c) A5 contributors
+
+use std::collections::HashSet;
+
+use a5::coordinate_systems::Cartesian;
+use a5::core::coordinate_transforms::{from_lon_lat, to_cartesian};
+use a5::traversal::global_neighbors::get_global_cell_neighbors;
+use a5::{
+    cell_to_boundary, cell_to_edges, cell_to_lonlat, cells_to_edge, edge_to_boundary,
+    edge_to_cells, get_res0_cells, is_valid_cell, is_valid_edge, lonlat_to_cell, A5Error, EdgeId,
+    LonLat, MAX_EDGE_RESOLUTION, WORLD_CELL,
+};
+
+fn cartesian(point: LonLat) -> Cartesian {
+    to_cartesian(from_lon_lat(point))
+}
+
+fn distance(p: Cartesian, q: Cartesian) -> f64 {
+    ((p.x() - q.x()).powi(2) + (p.y() - q.y()).powi(2) + (p.z() - q.z()).powi(2)).sqrt()
+}
+
+/// Whether `c` lies to the left of the great circle from `a` to `b`,
+/// computed relative to `a` to keep precision for tiny cells
+fn is_left(a: Cartesian, b: Cartesian, c: Cartesian) -> bool {
+    let (b, c) = (
+        Cartesian::new(b.x() - a.x(), b.y() - a.y(), b.z() - a.z()),
+        Cartesian::new(c.x() - a.x(), c.y() - a.y(), c.z() - a.z()),
+    );
+    a.x() * (b.y() * c.z() - b.z() * c.y())
+        + a.y() * (b.z() * c.x() - b.x() * c.z())
+        + a.z() * (b.x() * c.y() - b.y() * c.x())
+        > 0.0
+}
+
+fn sample_cells() -> Vec<u64> {
+    let mut cells = get_res0_cells().unwrap();
+    // Points on face edges and corners, so edges cross faces and quintants
+    let points = [
+        (0.0, 0.0),
+        (-31.7, 58.3),
+        (138.2, -26.6),
+        (179.9, 10.8),
+        (36.0, 52.6),
+    ];
+    for resolution in [1, 2, 3, 7, 14, MAX_EDGE_RESOLUTION] {
+        for (lon, lat) in points {
+            cells.push(lonlat_to_cell(LonLat::new(lon, lat), resolution).unwrap());
+        }
+    }
+    cells
+}
+
+#[test]
+fn test_edges_lead_to_every_neighbor() {
+    for cell in sample_cells() {
+        let edges = cell_to_edges(cell).unwrap();
+        let mut destinations = HashSet::new();
+        for &edge in &edges {
+            assert!(is_valid_edge(edge.get()));
+            assert!(!is_valid_cell(edge.get()));
+            assert_eq!(EdgeId::try_from(edge.get()).unwrap(), edge);
+            let (origin, destination) = edge_to_cells(edge).unwrap();
+            assert_eq!(origin, cell);
+            assert_eq!(cells_to_edge(origin, destination).unwrap(), edge);
+            destinations.insert(destination);
+        }
+        let neighbors: HashSet<u64> = get_global_cell_neighbors(cell, true).into_iter().collect();
+        assert_eq!(destinations, neighbors, "cell {:x}", cell);
+        assert_eq!(edges.len(), neighbors.len());
+    }
+}
+
+#[test]
+fn test_edge_boundaries() {
+    for cell in sample_cells() {
+        let ring = cell_to_boundary(cell, None).unwrap();
+        let center = cartesian(cell_to_lonlat(cell).unwrap());
+        for edge in cell_to_edges(cell).unwrap() {
+            let [start, end] = edge_to_boundary(edge).unwrap();
+            let (a, b) = (cartesian(start), cartesian(end));
+
+            // Both ends are vertices of the cell boundary
+            for p in [a, b] {
+                assert!(ring.iter().any(|&v| distance(cartesian(v), p) < 1e-12));
+            }
+            // The origin lies to the left
+            assert!(is_left(a, b, center), "edge {}", edge);
+
+            // The reverse edge runs the other way
+            let (_, destination) = edge_to_cells(edge).unwrap();
+            let reverse = cells_to_edge(destination, cell).unwrap();
+            let [reverse_start, reverse_end] = edge_to_boundary(reverse).unwrap();
+            for (p, q) in [(a, cartesian(reverse_end)), (b, cartesian(reverse_start))] {
+                assert!(distance(p, q) < 1e-12, "edge {}", edge);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_invalid_edges() {
+    let cell = lonlat_to_cell(LonLat::new(10.0, 20.0), 8).unwrap();
+    let edge = cell_to_edges(cell).unwrap()[0].get();
+    assert!(!is_valid_edge(cell));
+    // Index 5 is past the last edge
+    assert!(!is_valid_edge(edge | 5 << 56));
+    assert!(matches!(EdgeId::try_from(cell), Err(A5Error::InvalidEdge(e)) if e == cell));
+
+    let res1 = lonlat_to_cell(LonLat::new(10.0, 20.0), 1).unwrap();
+    let edge = cell_to_edges(res1).unwrap()[0].get();
+    assert!(is_valid_edge(edge | 2 << 56));
+    assert!(!is_valid_edge(edge | 3 << 56));
+
+    // Too fine to tag
+    let fine = lonlat_to_cell(LonLat::new(10.0, 20.0), MAX_EDGE_RESOLUTION + 1).unwrap();
+    assert!(matches!(
+        cell_to_edges(fine),
+        Err(A5Error::InvalidResolution(26))
+    ));
+    assert!(matches!(
+        cell_to_edges(WORLD_CELL),
+        Err(A5Error::InvalidResolution(-1))
+    ));
+
+    let far = lonlat_to_cell(LonLat::new(-100.0, -20.0), 8).unwrap();
+    assert!(matches!(
+        cells_to_edge(cell, far),
+        Err(A5Error::NotNeighbors { origin, destination }) if origin == cell && destination == far
+    ));
+}
+
+#[test]
+fn test_edge_ids_are_not_cells() {
+    // The old `cell | (index + 1)` ids doubled as resolution-29 and -30 cells
+    for resolution in [0, 1, 2, 15, 24, MAX_EDGE_RESOLUTION] {
+        let cell = lonlat_to_cell(LonLat::new(-31.7, 58.3), resolution).unwrap();
+        assert!(!is_valid_edge(cell));
+        for edge in cell_to_edges(cell).unwrap() {
+            assert!(!is_valid_cell(edge.get()));
+        }
+    }
+    assert!(is_valid_cell(0x5146000000000001));
+    assert!(!is_valid_edge(0x5146000000000001));
+}
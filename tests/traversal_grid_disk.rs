@@ -2,7 +2,10 @@
 // SPDX-License-Identifier: Apache-2.0
 // Copyright (c) A5 contributors
 
-use a5::{get_resolution, grid_disk, grid_disk_vertex, hex_to_u64, u64_to_hex, uncompact};
+use a5::{
+    get_resolution, grid_disk, grid_disk_distances, grid_disk_distances_vertex, grid_disk_vertex,
+    grid_distance, grid_ring, grid_ring_vertex, hex_to_u64, u64_to_hex, uncompact, WORLD_CELL,
+};
 use serde::Deserialize;
 
 #[derive(Deserialize)]
@@ -90,3 +93,74 @@ fn test_grid_disk_k0() {
         .collect();
     assert_eq!(result, vec![fixtures[0].cell_id.clone()]);
 }
+
+fn sorted_disk(disk: Vec<u64>, resolution: i32) -> Vec<u64> {
+    let mut cells = uncompact(&disk, resolution).unwrap();
+    cells.sort_unstable();
+    cells
+}
+
+#[test]
+fn test_grid_disk_distances() {
+    for f in &load_fixtures() {
+        let cell_id = hex_to_u64(&f.cell_id).unwrap();
+        let res = get_resolution(cell_id);
+        let variants = [
+            (
+                grid_disk_distances(cell_id, f.k).unwrap(),
+                grid_disk as fn(u64, usize) -> _,
+            ),
+            (
+                grid_disk_distances_vertex(cell_id, f.k).unwrap(),
+                grid_disk_vertex,
+            ),
+        ];
+        for (rings, disk) in variants {
+            assert_eq!(rings.len(), f.k + 1);
+            assert_eq!(rings[0], vec![cell_id]);
+
+            // Ring i is the disk of radius i without the disk of radius i - 1
+            let mut previous: Vec<u64> = Vec::new();
+            for (i, ring) in rings.iter().enumerate() {
+                let current = sorted_disk(disk(cell_id, i).unwrap(), res);
+                let expected: Vec<u64> = current
+                    .iter()
+                    .copied()
+                    .filter(|c| previous.binary_search(c).is_err())
+                    .collect();
+                assert_eq!(ring, &expected, "cellId={} ring={}", f.cell_id, i);
+                previous = current;
+            }
+        }
+    }
+}
+
+#[test]
+fn test_grid_ring() {
+    for f in load_fixtures().iter().take(5) {
+        let cell_id = hex_to_u64(&f.cell_id).unwrap();
+        let rings = grid_disk_distances(cell_id, f.k).unwrap();
+        let vertex_rings = grid_disk_distances_vertex(cell_id, f.k).unwrap();
+        for k in 0..=f.k {
+            assert_eq!(grid_ring(cell_id, k).unwrap(), rings[k]);
+            assert_eq!(grid_ring_vertex(cell_id, k).unwrap(), vertex_rings[k]);
+        }
+        for &cell in rings[f.k].iter().step_by(5) {
+            assert_eq!(grid_distance(cell_id, cell).unwrap(), f.k as u64);
+        }
+    }
+
+    let invalid = 63 << 58 | 1 << 56;
+    assert!(grid_ring(invalid, 1).is_err());
+    assert!(grid_disk_distances_vertex(invalid, 0).is_err());
+}
+
+#[test]
+fn test_world_cell_rings_are_empty() {
+    assert_eq!(grid_ring(WORLD_CELL, 0).unwrap(), vec![WORLD_CELL]);
+    assert!(grid_ring(WORLD_CELL, 1).unwrap().is_empty());
+    assert!(grid_ring_vertex(WORLD_CELL, 2).unwrap().is_empty());
+    let expected = vec![vec![WORLD_CELL], vec![], vec![]];
+    assert_eq!(grid_disk_distances(WORLD_CELL, 2).unwrap(), expected);
+    assert_eq!(grid_disk_distances_vertex(WORLD_CELL, 2).unwrap(), expected);
+}
@@ -0,0 +1,135 @@
+// A5
+// SPDX-License-Identifier: Apache-2.0
+// Copyright (c) A5 contributors
+
+use std::collections::{HashMap, VecDeque};
+
+use a5::core::serialization::{cell_to_children, WORLD_CELL};
+use a5::traversal::global_neighbors::get_global_cell_neighbors;
+use a5::{grid_distance, grid_path_cells, lonlat_to_cell, A5Error, LonLat, MAX_GRID_DISTANCE};
+
+/// Hop counts from `start` to every cell within `max_hops`, by plain BFS
+fn bfs_distances(start: u64, max_hops: u64) -> HashMap<u64, u64> {
+    let mut distances = HashMap::from([(start, 0)]);
+    let mut queue = VecDeque::from([start]);
+    while let Some(cell) = queue.pop_front() {
+        let hops = distances[&cell];
+        if hops == max_hops {
+            continue;
+        }
+        for neighbor in get_global_cell_neighbors(cell, true) {
+            distances.entry(neighbor).or_insert_with(|| {
+                queue.push_back(neighbor);
+                hops + 1
+            });
+        }
+    }
+    distances
+}
+
+fn assert_valid_path(path: &[u64], start: u64, end: u64) {
+    assert_eq!(path.first(), Some(&start));
+    assert_eq!(path.last(), Some(&end));
+    for pair in path.windows(2) {
+        assert!(
+            get_global_cell_neighbors(pair[0], true).contains(&pair[1]),
+            "{:x} and {:x} do not share an edge",
+            pair[0],
+            pair[1]
+        );
+    }
+}
+
+#[test]
+fn test_grid_distance_matches_bfs() {
+    // Starts near face corners and edges, so the searches cross faces
+    let points = [
+        (0.0, 0.0),
+        (-31.7, 58.3),
+        (138.2, -26.6),
+        (179.9, 10.8),
+        (36.0, 90.0),
+    ];
+    for (lon, lat) in points {
+        for (resolution, max_hops) in [(3, 5), (6, 7)] {
+            let start = lonlat_to_cell(LonLat::new(lon, lat), resolution).unwrap();
+            let distances = bfs_distances(start, max_hops);
+            for (&end, &hops) in distances.iter().step_by(7) {
+                assert_eq!(grid_distance(start, end).unwrap(), hops);
+                let path = grid_path_cells(start, end).unwrap();
+                assert_eq!(path.len() as u64, hops + 1);
+                assert_valid_path(&path, start, end);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_low_resolutions() {
+    let mut cells = cell_to_children(WORLD_CELL, Some(1)).unwrap();
+    cells.sort_unstable();
+    for &start in cells.iter().step_by(11) {
+        let distances = bfs_distances(start, u64::MAX);
+        assert_eq!(distances.len(), cells.len());
+        for &end in &cells {
+            assert_eq!(grid_distance(start, end).unwrap(), distances[&end]);
+        }
+    }
+    let origins = cell_to_children(WORLD_CELL, Some(0)).unwrap();
+    let path = grid_path_cells(origins[0], origins[11]).unwrap();
+    assert_valid_path(&path, origins[0], origins[11]);
+}
+
+#[test]
+fn test_long_paths() {
+    let start = lonlat_to_cell(LonLat::new(-3.7, 40.4), 8).unwrap();
+    let end = lonlat_to_cell(LonLat::new(12.5, 41.9), 8).unwrap();
+    let path = grid_path_cells(start, end).unwrap();
+    assert_valid_path(&path, start, end);
+    assert!(path.len() > 20);
+
+    let distance = grid_distance(start, end).unwrap();
+    assert_eq!(path.len() as u64, distance + 1);
+    assert_eq!(grid_distance(end, start).unwrap(), distance);
+}
+
+#[test]
+fn test_same_cell_and_errors() {
+    let cell = lonlat_to_cell(LonLat::new(10.0, 20.0), 5).unwrap();
+    assert_eq!(grid_distance(cell, cell).unwrap(), 0);
+    assert_eq!(grid_path_cells(cell, cell).unwrap(), vec![cell]);
+
+    let coarser = lonlat_to_cell(LonLat::new(10.0, 20.0), 4).unwrap();
+    assert!(matches!(
+        grid_distance(cell, coarser),
+        Err(A5Error::ResolutionMismatch { start: 5, end: 4 })
+    ));
+    assert!(matches!(
+        grid_path_cells(coarser, cell),
+        Err(A5Error::ResolutionMismatch { start: 4, end: 5 })
+    ));
+
+    let invalid = 63 << 58 | 1 << 56;
+    assert!(grid_distance(invalid, cell).is_err());
+    assert!(grid_path_cells(cell, invalid).is_err());
+}
+
+#[test]
+fn test_distance_limit() {
+    // Well within the limit at a coarse resolution, far beyond it at a fine one
+    let (madrid, rome) = (LonLat::new(-3.7, 40.4), LonLat::new(12.5, 41.9));
+    let start = lonlat_to_cell(madrid, 10).unwrap();
+    let end = lonlat_to_cell(rome, 10).unwrap();
+    assert!(grid_distance(start, end).unwrap() < MAX_GRID_DISTANCE);
+
+    let start = lonlat_to_cell(madrid, 20).unwrap();
+    let end = lonlat_to_cell(rome, 20).unwrap();
+    assert_eq!(
+        grid_distance(start, end),
+        Err(A5Error::PathSearchLimit { start, end })
+    );
+    assert!(matches!(
+        grid_path_cells(end, start),
+        Err(A5Error::PathSearchLimit { .. })
+    ));
+}
@@ -0,0 +1,167 @@
+// A5
+// SPDX-License-Identifier: Apache-2.0
+// Copyright (c) A5 contributors
+
+use std::collections::HashSet;
+
+use a5::core::coordinate_transforms::{from_lon_lat, to_cartesian};
+use a5::{
+    cell_to_boundary, cell_to_children, cell_to_edges, cell_to_vertices, get_res0_cells,
+    is_valid_cell, is_valid_edge, is_valid_vertex, lonlat_to_cell, vertex_to_cells,
+    vertex_to_lonlat, A5Error, CellToBoundaryOptions, LonLat, VertexId, MAX_EDGE_RESOLUTION,
+    WORLD_CELL,
+};
+
+fn distance(a: LonLat, b: LonLat) -> f64 {
+    let (a, b) = (to_cartesian(from_lon_lat(a)), to_cartesian(from_lon_lat(b)));
+    ((a.x() - b.x()).powi(2) + (a.y() - b.y()).powi(2) + (a.z() - b.z()).powi(2)).sqrt()
+}
+
+fn sample_cells() -> Vec<u64> {
+    let mut cells = get_res0_cells().unwrap();
+    // Points on face edges and corners, so vertices sit on seams
+    let points = [
+        (0.0, 0.0),
+        (-31.7, 58.3),
+        (138.2, -26.6),
+        (179.9, 10.8),
+        (36.0, 52.6),
+    ];
+    for resolution in [1, 2, 3, 7, 14, MAX_EDGE_RESOLUTION] {
+        for (lon, lat) in points {
+            cells.push(lonlat_to_cell(LonLat::new(lon, lat), resolution).unwrap());
+        }
+    }
+    cells
+}
+
+#[test]
+fn test_vertices_are_shared() {
+    for cell in sample_cells() {
+        let options = CellToBoundaryOptions::default()
+            .with_closed_ring(false)
+            .with_segments(1);
+        let boundary = cell_to_boundary(cell, Some(options)).unwrap();
+        let vertices = cell_to_vertices(cell).unwrap();
+        assert_eq!(vertices.len(), boundary.len());
+
+        for vertex in vertices {
+            assert!(is_valid_vertex(vertex.get()));
+            assert_eq!(VertexId::try_from(vertex.get()).unwrap(), vertex);
+
+            let position = vertex_to_lonlat(vertex).unwrap();
+            assert!(boundary.iter().any(|&p| distance(p, position) < 1e-12));
+
+            // Every cell meeting at the vertex gives it the same id
+            let cells = vertex_to_cells(vertex).unwrap();
+            assert!(
+                cells.len() >= 3 && cells.contains(&cell),
+                "cells {:x?}",
+                cells
+            );
+            for &other in &cells {
+                assert!(cell_to_vertices(other).unwrap().contains(&vertex));
+            }
+        }
+    }
+}
+
+#[test]
+fn test_vertex_counts() {
+    // Dodecahedron: 20 corners, each shared by 3 faces
+    let res0 = get_res0_cells().unwrap();
+    let vertices: HashSet<VertexId> = res0
+        .iter()
+        .flat_map(|&cell| cell_to_vertices(cell).unwrap())
+        .collect();
+    assert_eq!(vertices.len(), 20);
+    for vertex in &vertices {
+        assert_eq!(vertex_to_cells(*vertex).unwrap().len(), 3);
+    }
+
+    // Resolution 1: 12 face centres shared by 5 triangles, 20 corners by 6
+    let res1 = cell_to_children(WORLD_CELL, Some(1)).unwrap();
+    let vertices: HashSet<VertexId> = res1
+        .iter()
+        .flat_map(|&cell| cell_to_vertices(cell).unwrap())
+        .collect();
+    assert_eq!(vertices.len(), 32);
+    let mut degrees: Vec<usize> = vertices
+        .iter()
+        .map(|v| vertex_to_cells(*v).unwrap().len())
+        .collect();
+    degrees.sort_unstable();
+    assert_eq!(degrees, [vec![5; 12], vec![6; 20]].concat());
+
+    // Resolution 2: Euler's formula with 240 pentagons and 600 edges
+    let res2 = cell_to_children(WORLD_CELL, Some(2)).unwrap();
+    let vertices: HashSet<VertexId> = res2
+        .iter()
+        .flat_map(|&cell| cell_to_vertices(cell).unwrap())
+        .collect();
+    assert_eq!(vertices.len(), 600 + 2 - 240);
+}
+
+/// Raw id of vertex `index` of `cell`, in the layout described in
+/// `traversal::edge`, whether or not `cell` owns the vertex
+fn raw_vertex_id(cell: u64, index: usize) -> u64 {
+    0b1111 << 60 | 1 << 59 | (index as u64) << 56 | cell >> 8
+}
+
+#[test]
+fn test_invalid_vertices() {
+    let cell = lonlat_to_cell(LonLat::new(10.0, 20.0), 6).unwrap();
+    assert!(!is_valid_vertex(cell));
+    assert!(!is_valid_vertex(raw_vertex_id(cell, 5)));
+    assert!(matches!(
+        VertexId::try_from(raw_vertex_id(cell, 7)),
+        Err(A5Error::InvalidVertex(_))
+    ));
+
+    // Only the owner, the smallest cell at the vertex, gives a valid id
+    for vertex in cell_to_vertices(cell).unwrap() {
+        let cells = vertex_to_cells(vertex).unwrap();
+        let index_in = |other: u64| {
+            cell_to_vertices(other)
+                .unwrap()
+                .iter()
+                .position(|&v| v == vertex)
+                .unwrap()
+        };
+        assert_eq!(vertex.get(), raw_vertex_id(cells[0], index_in(cells[0])));
+        for &other in &cells[1..] {
+            assert!(!is_valid_vertex(raw_vertex_id(other, index_in(other))));
+        }
+    }
+
+    assert!(matches!(
+        cell_to_vertices(WORLD_CELL),
+        Err(A5Error::InvalidResolution(-1))
+    ));
+    let fine = lonlat_to_cell(LonLat::new(10.0, 20.0), 30).unwrap();
+    assert!(matches!(
+        cell_to_vertices(fine),
+        Err(A5Error::InvalidResolution(30))
+    ));
+}
+
+#[test]
+fn test_vertex_ids_are_not_cells_or_edges() {
+    for resolution in [0, 1, 2, 15, MAX_EDGE_RESOLUTION] {
+        let cell = lonlat_to_cell(LonLat::new(-31.7, 58.3), resolution).unwrap();
+        let edges: HashSet<u64> = cell_to_edges(cell)
+            .unwrap()
+            .iter()
+            .map(|edge| edge.get())
+            .collect();
+        for vertex in cell_to_vertices(cell).unwrap() {
+            assert!(!is_valid_edge(vertex.get()));
+            assert!(!is_valid_cell(vertex.get()));
+            assert!(!edges.contains(&vertex.get()));
+        }
+        for &edge in &edges {
+            assert!(!is_valid_vertex(edge));
+        }
+        assert!(!is_valid_vertex(cell));
+    }
+}
@@ -1,5 +1,8 @@
 use a5::coordinate_systems::Cartesian;
-use a5::utils::great_circle::{great_circle_distance, sample_great_circle_arc};
+use a5::core::constants::AUTHALIC_RADIUS_EARTH;
+use a5::utils::great_circle::{
+    cross_track_distance, great_circle_distance, sample_great_circle_arc, short_arc_distance,
+};
 use serde::Deserialize;
 use std::fs;
 
@@ -68,3 +71,32 @@ fn test_sample_great_circle_arc_fixtures() {
         }
     }
 }
+
+fn unit(x: f64, y: f64, z: f64) -> Cartesian {
+    let length = (x * x + y * y + z * z).sqrt();
+    Cartesian::new(x / length, y / length, z / length)
+}
+
+#[test]
+fn test_short_arc_distance() {
+    let a = unit(1.0, 0.0, 0.0);
+    for angle in [1.0f64, 1e-3, 1e-6, 1e-10] {
+        let b = unit(angle.cos(), angle.sin(), 0.0);
+        let expected = angle * AUTHALIC_RADIUS_EARTH;
+        assert!((short_arc_distance(a, b) / expected - 1.0).abs() < 1e-9);
+    }
+    let b = unit(0.0, 1.0, 0.0);
+    assert!((short_arc_distance(a, b) - great_circle_distance(a, b)).abs() < 1e-6);
+}
+
+#[test]
+fn test_cross_track_distance() {
+    // Along the equator, a point at latitude θ is θ away from it
+    let (a, b) = (unit(1.0, 0.0, 0.0), unit(1.0, 1e-7, 0.0));
+    for angle in [0.5f64, 1e-9] {
+        let point = unit(angle.cos(), 0.0, angle.sin());
+        let expected = angle * AUTHALIC_RADIUS_EARTH;
+        assert!((cross_track_distance(point, a, b) / expected - 1.0).abs() < 1e-6);
+    }
+    assert_eq!(cross_track_distance(unit(0.0, 1.0, 0.0), a, b), 0.0);
+}
@@ -0,0 +1,172 @@
+// A5
+// SPDX-License-Identifier: Apache-2.0
+// Copyright (c) A5 contributors
+
+use a5::io::geometry::{geometry_to_cells, Geometry, MAX_NESTING_DEPTH};
+use a5::io::wkb::{geometry_to_wkb, parse_wkb};
+use a5::io::wkt::parse_wkt;
+use a5::{
+    cell_to_wkb, cell_to_wkt, cells_to_wkb, cells_to_wkt, grid_disk, lonlat_to_cell, uncompact,
+    wkb_to_cells, wkt_to_cells, A5Error, LonLat, WORLD_CELL,
+};
+
+#[test]
+fn test_point_encoding() {
+    // Little-endian ISO WKB for POINT (1 2)
+    let mut expected = vec![1, 1, 0, 0, 0];
+    expected.extend_from_slice(&1.0f64.to_le_bytes());
+    expected.extend_from_slice(&2.0f64.to_le_bytes());
+    let point = Geometry::Point(LonLat::new(1.0, 2.0));
+    assert_eq!(geometry_to_wkb(&point), expected);
+    assert_eq!(parse_wkb(&expected).unwrap(), point);
+}
+
+#[test]
+fn test_cell_writers_match_wkt() {
+    let center = lonlat_to_cell(LonLat::new(-73.9857, 40.7484), 7).unwrap();
+    let wkb = cell_to_wkb(center, None).unwrap();
+    let wkt = cell_to_wkt(center, None).unwrap();
+    assert_eq!(parse_wkb(&wkb).unwrap(), parse_wkt(&wkt).unwrap());
+
+    let cells = uncompact(&grid_disk(center, 3).unwrap(), 7).unwrap();
+    let wkb = cells_to_wkb(&cells, None).unwrap();
+    let wkt = cells_to_wkt(&cells, None).unwrap();
+    assert_eq!(parse_wkb(&wkb).unwrap(), parse_wkt(&wkt).unwrap());
+
+    // The world cell is a polygon with no rings
+    let wkb = cell_to_wkb(WORLD_CELL, None).unwrap();
+    assert_eq!(wkb, vec![1, 3, 0, 0, 0, 0, 0, 0, 0]);
+    assert_eq!(parse_wkb(&wkb).unwrap(), Geometry::Polygon(Vec::new()));
+}
+
+#[test]
+fn test_round_trip() {
+    let wkt = "GEOMETRYCOLLECTION (MULTIPOINT ((1 2), (3 4)), \
+               MULTILINESTRING ((0 0, 1 1), (2 2, 3 3)), \
+               MULTIPOLYGON (((0 0, 1 0, 1 1, 0 0)), ((5 5, 6 5, 6 6, 5 5))))";
+    let geometry = parse_wkt(wkt).unwrap();
+    assert_eq!(parse_wkb(&geometry_to_wkb(&geometry)).unwrap(), geometry);
+}
+
+#[test]
+fn test_big_endian_ewkb_with_z_and_srid() {
+    // EWKB POINT Z with SRID 4326, big endian
+    let mut wkb = vec![0];
+    wkb.extend_from_slice(&(0x8000_0000u32 | 0x2000_0000 | 1).to_be_bytes());
+    wkb.extend_from_slice(&4326u32.to_be_bytes());
+    for value in [10.0f64, 45.0, 250.0] {
+        wkb.extend_from_slice(&value.to_be_bytes());
+    }
+    assert_eq!(
+        parse_wkb(&wkb).unwrap(),
+        Geometry::Point(LonLat::new(10.0, 45.0))
+    );
+
+    // ISO POINT M (type 2001), little endian
+    let mut wkb = vec![1];
+    wkb.extend_from_slice(&2001u32.to_le_bytes());
+    for value in [10.0f64, 45.0, 7.0] {
+        wkb.extend_from_slice(&value.to_le_bytes());
+    }
+    assert_eq!(
+        parse_wkb(&wkb).unwrap(),
+        Geometry::Point(LonLat::new(10.0, 45.0))
+    );
+}
+
+#[test]
+fn test_parse_wkb_invalid() {
+    let point = geometry_to_wkb(&Geometry::Point(LonLat::new(1.0, 2.0)));
+    let mut trailing = point.clone();
+    trailing.push(0);
+    let mut bad_order = point.clone();
+    bad_order[0] = 7;
+    // LINESTRING claiming a billion points
+    let mut huge = vec![1, 2, 0, 0, 0];
+    huge.extend_from_slice(&1_000_000_000u32.to_le_bytes());
+    // MULTIPOLYGON whose member is a point
+    let mut wrong_member = vec![1, 6, 0, 0, 0, 1, 0, 0, 0];
+    wrong_member.extend_from_slice(&point);
+
+    for wkb in [
+        vec![],
+        point[..10].to_vec(),
+        trailing,
+        bad_order,
+        huge,
+        wrong_member,
+    ] {
+        assert!(
+            matches!(parse_wkb(&wkb), Err(A5Error::InvalidGeometry(_))),
+            "{:?} should fail",
+            wkb
+        );
+    }
+}
+
+/// `depth` GEOMETRYCOLLECTIONs, each holding the next, the last one empty
+fn nested_collections_wkb(depth: usize) -> Vec<u8> {
+    let mut wkb = Vec::with_capacity(9 * depth);
+    for _ in 1..depth {
+        wkb.extend_from_slice(&[1, 7, 0, 0, 0, 1, 0, 0, 0]);
+    }
+    wkb.extend_from_slice(&[1, 7, 0, 0, 0, 0, 0, 0, 0]);
+    wkb
+}
+
+#[test]
+fn test_parse_wkb_nesting_depth() {
+    assert!(parse_wkb(&nested_collections_wkb(MAX_NESTING_DEPTH)).is_ok());
+    for depth in [MAX_NESTING_DEPTH + 1, 20_000] {
+        assert!(matches!(
+            parse_wkb(&nested_collections_wkb(depth)),
+            Err(A5Error::InvalidGeometry(_))
+        ));
+    }
+}
+
+#[test]
+fn test_non_finite_positions_are_rejected() {
+    let point_wkb = |longitude: f64, latitude: f64| {
+        let mut wkb = vec![1, 1, 0, 0, 0];
+        wkb.extend_from_slice(&longitude.to_le_bytes());
+        wkb.extend_from_slice(&latitude.to_le_bytes());
+        wkb
+    };
+    for (longitude, latitude) in [
+        (f64::NAN, 0.0),
+        (f64::INFINITY, 0.0),
+        (0.0, f64::NEG_INFINITY),
+    ] {
+        let wkb = point_wkb(longitude, latitude);
+        assert!(matches!(parse_wkb(&wkb), Err(A5Error::InvalidGeometry(_))));
+        assert!(matches!(
+            wkb_to_cells(&wkb, 5),
+            Err(A5Error::InvalidGeometry(_))
+        ));
+    }
+    let polygon = Geometry::Polygon(vec![vec![
+        LonLat::new(0.0, 0.0),
+        LonLat::new(f64::INFINITY, 1.0),
+        LonLat::new(1.0, 1.0),
+    ]]);
+    assert!(matches!(
+        geometry_to_cells(&polygon, 5),
+        Err(A5Error::InvalidGeometry(_))
+    ));
+
+    // Both ordinates NaN is the encoding of an empty point
+    let empty = point_wkb(f64::NAN, f64::NAN);
+    assert_eq!(parse_wkb(&empty).unwrap(), Geometry::MultiPoint(Vec::new()));
+    assert!(wkb_to_cells(&empty, 5).unwrap().is_empty());
+}
+
+#[test]
+fn test_wkb_to_cells() {
+    let wkt = "MULTIPOLYGON (((10 45, 11 45, 11 46, 10 46, 10 45)), ((20 0, 21 0, 21 1, 20 0)))";
+    let wkb = geometry_to_wkb(&parse_wkt(wkt).unwrap());
+    assert_eq!(
+        wkb_to_cells(&wkb, 6).unwrap(),
+        wkt_to_cells(wkt, 6).unwrap()
+    );
+}
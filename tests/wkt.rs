@@ -0,0 +1,165 @@
+// A5
+// SPDX-License-Identifier: Apache-2.0
+// Copyright (c) A5 contributors
+
+use a5::io::geometry::{Geometry, MAX_NESTING_DEPTH};
+use a5::io::wkt::{geometry_to_wkt, parse_wkt};
+use a5::{
+    cell_to_boundary, cell_to_wkt, cells_to_wkt, grid_disk, line_string_to_cells, lonlat_to_cell,
+    polygon_to_cells, uncompact, wkt_to_cells, A5Error, CellToBoundaryOptions, LonLat, WORLD_CELL,
+};
+
+fn square(lon: f64, lat: f64, size: f64) -> Vec<LonLat> {
+    vec![
+        LonLat::new(lon, lat),
+        LonLat::new(lon + size, lat),
+        LonLat::new(lon + size, lat + size),
+        LonLat::new(lon, lat + size),
+    ]
+}
+
+#[test]
+fn test_cell_to_wkt() {
+    let cell = lonlat_to_cell(LonLat::new(10.0, 45.0), 5).unwrap();
+    let wkt = cell_to_wkt(cell, Some(1)).unwrap();
+    assert!(wkt.starts_with("POLYGON (("));
+
+    // Round trips to the closed cell_to_boundary ring
+    let options = CellToBoundaryOptions::default().with_segments(1);
+    let boundary = cell_to_boundary(cell, Some(options)).unwrap();
+    assert_eq!(parse_wkt(&wkt).unwrap(), Geometry::Polygon(vec![boundary]));
+
+    // The world cell has no boundary
+    let wkt = cell_to_wkt(WORLD_CELL, None).unwrap();
+    assert_eq!(wkt, "POLYGON EMPTY");
+    assert_eq!(parse_wkt(&wkt).unwrap(), Geometry::Polygon(Vec::new()));
+}
+
+#[test]
+fn test_cells_to_wkt_dissolves() {
+    let center = lonlat_to_cell(LonLat::new(10.0, 45.0), 6).unwrap();
+    let cells = uncompact(&grid_disk(center, 2).unwrap(), 6).unwrap();
+    let wkt = cells_to_wkt(&cells, Some(1)).unwrap();
+    match parse_wkt(&wkt).unwrap() {
+        Geometry::MultiPolygon(polygons) => {
+            assert_eq!(polygons.len(), 1);
+            assert_eq!(polygons[0].len(), 1);
+        }
+        other => panic!("expected MULTIPOLYGON, got {:?}", other),
+    }
+    assert_eq!(cells_to_wkt(&[], None).unwrap(), "MULTIPOLYGON EMPTY");
+}
+
+#[test]
+fn test_writer_closes_rings_once() {
+    let open = square(0.0, 0.0, 1.0);
+    let mut closed = open.clone();
+    closed.push(open[0]);
+    let expected = "POLYGON ((0 0, 1 0, 1 1, 0 1, 0 0))";
+    assert_eq!(geometry_to_wkt(&Geometry::Polygon(vec![open])), expected);
+    assert_eq!(geometry_to_wkt(&Geometry::Polygon(vec![closed])), expected);
+}
+
+#[test]
+fn test_parse_wkt_types() {
+    assert_eq!(
+        parse_wkt("point z (1.5 -2 100)").unwrap(),
+        Geometry::Point(LonLat::new(1.5, -2.0))
+    );
+    assert_eq!(
+        parse_wkt("SRID=4326;MULTIPOINT (1 2, (3 4))").unwrap(),
+        Geometry::MultiPoint(vec![LonLat::new(1.0, 2.0), LonLat::new(3.0, 4.0)])
+    );
+    assert_eq!(
+        parse_wkt("POINT EMPTY").unwrap(),
+        Geometry::MultiPoint(Vec::new())
+    );
+    assert_eq!(
+        parse_wkt("MULTIPOLYGON (((0 0, 1 0, 1 1, 0 0)), EMPTY)").unwrap(),
+        Geometry::MultiPolygon(vec![
+            vec![vec![
+                LonLat::new(0.0, 0.0),
+                LonLat::new(1.0, 0.0),
+                LonLat::new(1.0, 1.0),
+                LonLat::new(0.0, 0.0),
+            ]],
+            vec![],
+        ])
+    );
+
+    let collection = "GEOMETRYCOLLECTION (POINT (1 2), LINESTRING (0 0, 1e1 -1.5E-1))";
+    let geometry = parse_wkt(collection).unwrap();
+    assert_eq!(
+        geometry,
+        Geometry::GeometryCollection(vec![
+            Geometry::Point(LonLat::new(1.0, 2.0)),
+            Geometry::LineString(vec![LonLat::new(0.0, 0.0), LonLat::new(10.0, -0.15)]),
+        ])
+    );
+    assert_eq!(parse_wkt(&geometry_to_wkt(&geometry)).unwrap(), geometry);
+}
+
+#[test]
+fn test_parse_wkt_invalid() {
+    for wkt in [
+        "",
+        "POINT",
+        "POINT (1)",
+        "POINT (1 2",
+        "POINT (1 2) extra",
+        "POINT (1 2, 3 4)",
+        "CIRCLE (0 0)",
+        "LINESTRING (0 0; 1 1)",
+        "SRID=4326 POINT (1 2)",
+        "POINT (-nan 0)",
+        "POINT (+inf 0)",
+        "POINT (1e999 0)",
+    ] {
+        assert!(
+            matches!(parse_wkt(wkt), Err(A5Error::InvalidGeometry(_))),
+            "{:?} should fail",
+            wkt
+        );
+    }
+}
+
+/// `depth` GEOMETRYCOLLECTIONs, each holding the next, the last one empty
+fn nested_collections_wkt(depth: usize) -> String {
+    format!(
+        "{}GEOMETRYCOLLECTION EMPTY{}",
+        "GEOMETRYCOLLECTION (".repeat(depth - 1),
+        ")".repeat(depth - 1)
+    )
+}
+
+#[test]
+fn test_parse_wkt_nesting_depth() {
+    assert!(parse_wkt(&nested_collections_wkt(MAX_NESTING_DEPTH)).is_ok());
+    for depth in [MAX_NESTING_DEPTH + 1, 20_000] {
+        assert!(matches!(
+            parse_wkt(&nested_collections_wkt(depth)),
+            Err(A5Error::InvalidGeometry(_))
+        ));
+    }
+}
+
+#[test]
+fn test_wkt_to_cells() {
+    // Open and closed rings fill the same cells, as in polygon_to_cells
+    let ring = square(10.0, 45.0, 1.0);
+    let expected = polygon_to_cells(std::slice::from_ref(&ring), 7).unwrap();
+    assert_eq!(
+        wkt_to_cells("POLYGON ((10 45, 11 45, 11 46, 10 46, 10 45))", 7).unwrap(),
+        expected
+    );
+    assert_eq!(
+        wkt_to_cells("POLYGON ((10 45, 11 45, 11 46, 10 46))", 7).unwrap(),
+        expected
+    );
+
+    let mut line = line_string_to_cells(&ring, 7).unwrap();
+    line.sort_unstable();
+    line.dedup();
+    let cells = wkt_to_cells("LINESTRING (10 45, 11 45, 11 46, 10 46)", 7).unwrap();
+    assert_eq!(uncompact(&cells, 7).unwrap(), line);
+}